@@ -0,0 +1,387 @@
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::{format_ident, quote};
+use serde_json::Value;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote, Ident, LitStr, Token, TraitItemMethod,
+};
+
+// Interface method generated from a JSON ABI function
+pub struct AbiMethod {
+    pub method: TraitItemMethod,
+    // Original (Solidity) name, used for the selector
+    pub sol_name: String,
+}
+
+// Args of `interface_from_abi!`: an optional interface name, and the path to the JSON ABI
+pub struct AbiInterfaceArgs {
+    pub name: Option<Ident>,
+    pub path: LitStr,
+}
+
+impl Parse for AbiInterfaceArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = if input.peek(Ident) {
+            let name = input.parse::<Ident>()?;
+            input.parse::<Token![,]>()?;
+            Some(name)
+        } else {
+            None
+        };
+        let path = input.parse::<LitStr>()?;
+
+        Ok(Self { name, path })
+    }
+}
+
+impl AbiInterfaceArgs {
+    // Resolves the ABI path relative to the crate root
+    pub fn full_path(&self) -> PathBuf {
+        let root = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".into());
+        PathBuf::from(root).join(self.path.value())
+    }
+
+    // The interface is named after the file, unless explicitly set
+    pub fn interface_name(&self) -> Ident {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => {
+                let path = self.full_path();
+                let stem = path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .expect("Invalid ABI file name");
+                Ident::new(stem, self.path.span())
+            }
+        }
+    }
+}
+
+// Parse the functions of a JSON ABI (either a plain array, or an artifact with an `abi` field)
+pub fn parse_abi_methods(path: &PathBuf) -> Result<Vec<AbiMethod>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Unable to read ABI file {:?}: {}", path, e))?;
+    let json: Value =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid ABI file {:?}: {}", path, e))?;
+    let entries = match &json {
+        Value::Array(entries) => entries,
+        Value::Object(artifact) => artifact
+            .get("abi")
+            .and_then(Value::as_array)
+            .ok_or("Missing `abi` field")?,
+        _ => return Err("Expected a JSON ABI array".into()),
+    };
+
+    // Overloaded functions get a numeric suffix, in declaration order
+    let mut overloads: HashMap<String, usize> = HashMap::new();
+
+    entries
+        .iter()
+        .filter(|entry| entry.get("type").and_then(Value::as_str) == Some("function"))
+        .map(|entry| {
+            let sol_name = entry
+                .get("name")
+                .and_then(Value::as_str)
+                .ok_or("Missing function name")?;
+
+            let mut name = to_snake_case(sol_name);
+            let count = overloads.entry(name.clone()).or_default();
+            if *count > 0 {
+                name = format!("{}_{}", name, count);
+            }
+            *count += 1;
+
+            let method = abi_function_to_method(&name, entry)?;
+            Ok(AbiMethod { method, sol_name: sol_name.to_string() })
+        })
+        .collect()
+}
+
+fn abi_function_to_method(name: &str, entry: &Value) -> Result<TraitItemMethod, String> {
+    let name = rust_ident(name);
+
+    // View and pure functions only need a static context
+    let is_view = match entry.get("stateMutability").and_then(Value::as_str) {
+        Some(mutability) => mutability == "view" || mutability == "pure",
+        None => entry.get("constant").and_then(Value::as_bool).unwrap_or(false),
+    };
+    let receiver = if is_view { quote! { &self } } else { quote! { &mut self } };
+
+    let inputs = params(entry, "inputs")?;
+    let arg_names = arg_names(entry);
+
+    let outputs = params(entry, "outputs")?;
+    let output = match outputs.len() {
+        0 => quote! {},
+        1 => {
+            let ty = &outputs[0];
+            quote! { -> #ty }
+        }
+        _ => quote! { -> (#(#outputs),*) },
+    };
+
+    Ok(parse_quote! {
+        fn #name(#receiver #(, #arg_names: #inputs)*) #output;
+    })
+}
+
+// Args are named after the ABI params (in snake_case), falling back to `argN` when a param has
+// no name, or one that can't be used as an identifier
+fn arg_names(entry: &Value) -> Vec<Ident> {
+    let params = entry.get("inputs").and_then(Value::as_array).map_or(&[][..], Vec::as_slice);
+
+    let mut names: Vec<Ident> = Vec::new();
+    for (i, param) in params.iter().enumerate() {
+        let name = param
+            .get("name")
+            .and_then(Value::as_str)
+            .map(to_snake_case)
+            .and_then(|name| arg_ident(&name))
+            .unwrap_or_else(|| format_ident!("arg{}", i));
+
+        // Repeated names get the position of the param as a suffix
+        let name = if names.contains(&name) { format_ident!("{}_{}", name, i) } else { name };
+        names.push(name);
+    }
+
+    names
+}
+
+// Names that clash with rust keywords are used as raw identifiers, when allowed
+fn arg_ident(name: &str) -> Option<Ident> {
+    syn::parse_str::<Ident>(name)
+        .or_else(|_| syn::parse_str::<Ident>(&format!("r#{}", name)))
+        .ok()
+}
+
+fn params(entry: &Value, key: &str) -> Result<Vec<TokenStream>, String> {
+    match entry.get(key).and_then(Value::as_array) {
+        Some(params) => params.iter().map(param_type).collect(),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn param_type(param: &Value) -> Result<TokenStream, String> {
+    let sol_type = param
+        .get("type")
+        .and_then(Value::as_str)
+        .ok_or("Missing param type")?;
+
+    sol_type_to_rust_type(sol_type, param.get("components"))
+}
+
+// Helper function to convert solidity types to their rust equivalent
+pub fn sol_type_to_rust_type(
+    sol_type: &str,
+    components: Option<&Value>,
+) -> Result<TokenStream, String> {
+    // Arrays, from the outermost dimension
+    if let Some(inner) = sol_type.strip_suffix("[]") {
+        let inner = sol_type_to_rust_type(inner, components)?;
+        return Ok(quote! { alloc::vec::Vec<#inner> });
+    }
+    if let Some(stripped) = sol_type.strip_suffix(']') {
+        let (inner, size) = stripped.rsplit_once('[').ok_or("Invalid array type")?;
+        let size: usize = size.parse().map_err(|_| "Invalid array size")?;
+        let size = Literal::usize_unsuffixed(size);
+        let inner = sol_type_to_rust_type(inner, components)?;
+        return Ok(quote! { [#inner; #size] });
+    }
+
+    let ty = match sol_type {
+        "address" => quote! { alloy_core::primitives::Address },
+        "bool" => quote! { bool },
+        "string" => quote! { alloc::string::String },
+        "bytes" => quote! { alloy_core::primitives::Bytes },
+        "tuple" => {
+            let components = components
+                .and_then(Value::as_array)
+                .ok_or("Missing tuple components")?
+                .iter()
+                .map(param_type)
+                .collect::<Result<Vec<_>, _>>()?;
+            quote! { (#(#components,)*) }
+        }
+        b if b.starts_with("bytes") => {
+            let size: usize = b[5..].parse().map_err(|_| "Invalid fixed bytes size")?;
+            let size = Literal::usize_unsuffixed(size);
+            quote! { alloy_core::primitives::FixedBytes<#size> }
+        }
+        u if u.starts_with("uint") => {
+            let bits = if u.len() > 4 { &u[4..] } else { "256" };
+            let ident = format_ident!("U{}", bits);
+            quote! { alloy_core::primitives::aliases::#ident }
+        }
+        i if i.starts_with("int") => {
+            let bits = if i.len() > 3 { &i[3..] } else { "256" };
+            let ident = format_ident!("I{}", bits);
+            quote! { alloy_core::primitives::aliases::#ident }
+        }
+        other => return Err(format!("Unsupported type: {}", other)),
+    };
+
+    Ok(ty)
+}
+
+// Convert a (camelCase or SCREAMING_CASE) Solidity name to snake_case
+fn to_snake_case(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut result = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() {
+            let prev = if i > 0 { Some(chars[i - 1]) } else { None };
+            let next = chars.get(i + 1);
+
+            // Word boundaries: `aB`, `0B` and the last capital of an acronym (`ABc`)
+            let is_boundary = match prev {
+                Some(p) if p.is_ascii_lowercase() || p.is_ascii_digit() => true,
+                Some(p) if p.is_ascii_uppercase() => next.is_some_and(|n| n.is_ascii_lowercase()),
+                _ => false,
+            };
+            if is_boundary {
+                result.push('_');
+            }
+            result.push(c.to_ascii_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+// Names that clash with rust keywords are used as raw identifiers
+fn rust_ident(name: &str) -> Ident {
+    match syn::parse_str::<Ident>(name) {
+        Ok(ident) => ident,
+        Err(_) => Ident::new_raw(name, Span::call_site()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_to_snake_case() {
+        let cases = vec![
+            ("get", "get"),
+            ("getReserves", "get_reserves"),
+            ("token0", "token0"),
+            ("kLast", "k_last"),
+            ("price0CumulativeLast", "price0_cumulative_last"),
+            ("DOMAIN_SEPARATOR", "domain_separator"),
+            ("tokenURI", "token_uri"),
+            ("ERC20Token", "erc20_token"),
+        ];
+
+        for (sol_name, rust_name) in cases {
+            assert_eq!(to_snake_case(sol_name), rust_name);
+        }
+    }
+
+    #[test]
+    fn test_sol_to_rust_types() {
+        let tuple = json!([{ "type": "address" }, { "type": "bytes" }]);
+        let cases = vec![
+            ("address", None, quote! { alloy_core::primitives::Address }),
+            ("uint256", None, quote! { alloy_core::primitives::aliases::U256 }),
+            ("int24", None, quote! { alloy_core::primitives::aliases::I24 }),
+            ("bytes32", None, quote! { alloy_core::primitives::FixedBytes<32> }),
+            ("string[]", None, quote! { alloc::vec::Vec<alloc::string::String> }),
+            ("bool[2][]", None, quote! { alloc::vec::Vec<[bool; 2]> }),
+            (
+                "tuple",
+                Some(&tuple),
+                quote! { (alloy_core::primitives::Address, alloy_core::primitives::Bytes,) },
+            ),
+        ];
+
+        for (sol_type, components, rust_type) in cases {
+            assert_eq!(
+                sol_type_to_rust_type(sol_type, components).unwrap().to_string(),
+                rust_type.to_string()
+            );
+        }
+
+        assert!(sol_type_to_rust_type("function", None).is_err());
+        assert!(sol_type_to_rust_type("tuple", None).is_err());
+    }
+
+    #[test]
+    fn test_abi_function_to_method() {
+        let entry = json!({
+            "type": "function",
+            "name": "getReserves",
+            "inputs": [],
+            "outputs": [
+                { "name": "reserve0", "type": "uint112" },
+                { "name": "reserve1", "type": "uint112" },
+                { "name": "blockTimestampLast", "type": "uint32" },
+            ],
+            "stateMutability": "view",
+        });
+        let method = abi_function_to_method("get_reserves", &entry).unwrap();
+        let expected: TraitItemMethod = parse_quote! {
+            fn get_reserves(&self) -> (
+                alloy_core::primitives::aliases::U112,
+                alloy_core::primitives::aliases::U112,
+                alloy_core::primitives::aliases::U32
+            );
+        };
+        assert_eq!(quote! { #method }.to_string(), quote! { #expected }.to_string());
+
+        let entry = json!({
+            "type": "function",
+            "name": "transfer",
+            "inputs": [
+                { "name": "to", "type": "address" },
+                { "name": "value", "type": "uint256" },
+            ],
+            "outputs": [{ "name": "", "type": "bool" }],
+            "stateMutability": "nonpayable",
+        });
+        let method = abi_function_to_method("transfer", &entry).unwrap();
+        let expected: TraitItemMethod = parse_quote! {
+            fn transfer(
+                &mut self,
+                to: alloy_core::primitives::Address,
+                value: alloy_core::primitives::aliases::U256
+            ) -> bool;
+        };
+        assert_eq!(quote! { #method }.to_string(), quote! { #expected }.to_string());
+
+        // Missing and unusable names fall back to the position of the param
+        let entry = json!({
+            "type": "function",
+            "name": "swap",
+            "inputs": [
+                { "name": "", "type": "address" },
+                { "name": "type", "type": "uint8" },
+                { "name": "self", "type": "bool" },
+                { "name": "amountIn", "type": "uint256" },
+                { "name": "amount_in", "type": "uint256" },
+                { "type": "bytes" },
+            ],
+            "outputs": [],
+            "stateMutability": "nonpayable",
+        });
+        let method = abi_function_to_method("swap", &entry).unwrap();
+        let expected: TraitItemMethod = parse_quote! {
+            fn swap(
+                &mut self,
+                arg0: alloy_core::primitives::Address,
+                r#type: alloy_core::primitives::aliases::U8,
+                arg2: bool,
+                amount_in: alloy_core::primitives::aliases::U256,
+                amount_in_4: alloy_core::primitives::aliases::U256,
+                arg5: alloy_core::primitives::Bytes
+            );
+        };
+        assert_eq!(quote! { #method }.to_string(), quote! { #expected }.to_string());
+    }
+}
@@ -4,15 +4,20 @@ use alloy_core::primitives::keccak256;
 use alloy_dyn_abi::DynSolType;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
+use r55_types::rust_type_to_sol_type;
 use syn::{
     parse::{Parse, ParseStream},
     FnArg, Ident, ImplItemMethod, LitStr, PathArguments, ReturnType, TraitItemMethod, Type,
 };
 
-// Unified method info from `ImplItemMethod` and `TraitItemMethod`
+use crate::abi::AbiMethod;
+
+// Unified method info from `ImplItemMethod`, `TraitItemMethod` and `AbiMethod`
 #[derive(Clone)]
 pub struct MethodInfo<'a> {
     name: &'a Ident,
+    // Name used in the selector, when it differs from the rust one
+    sol_name: Option<&'a str>,
     args: Vec<syn::FnArg>,
     return_type: &'a ReturnType,
 }
@@ -21,6 +26,7 @@ impl<'a> From<&'a ImplItemMethod> for MethodInfo<'a> {
     fn from(method: &'a ImplItemMethod) -> Self {
         Self {
             name: &method.sig.ident,
+            sol_name: None,
             args: method.sig.inputs.iter().cloned().collect(),
             return_type: &method.sig.output,
         }
@@ -31,12 +37,22 @@ impl<'a> From<&'a TraitItemMethod> for MethodInfo<'a> {
     fn from(method: &'a TraitItemMethod) -> Self {
         Self {
             name: &method.sig.ident,
+            sol_name: None,
             args: method.sig.inputs.iter().cloned().collect(),
             return_type: &method.sig.output,
         }
     }
 }
 
+impl<'a> From<&'a AbiMethod> for MethodInfo<'a> {
+    fn from(method: &'a AbiMethod) -> Self {
+        Self {
+            sol_name: Some(method.sol_name.as_str()),
+            ..Self::from(&method.method)
+        }
+    }
+}
+
 impl<'a> MethodInfo<'a> {
     pub fn is_mutable(&self) -> bool {
         match self.args.first() {
@@ -142,6 +158,7 @@ where
         use core::marker::PhantomData;
         pub struct #interface_name<C: CallCtx> {
             address: Address,
+            gas_limit: Option<u64>,
             _ctx: PhantomData<C>
         }
 
@@ -159,6 +176,7 @@ where
             fn into_interface(self) -> #interface_name<C> {
                 #interface_name {
                     address: self.address,
+                    gas_limit: self.gas_limit,
                     _ctx: PhantomData
                 }
             }
@@ -170,6 +188,7 @@ where
             fn from_builder(builder: InterfaceBuilder<Self>) -> Self {
                 Self {
                     address: builder.address,
+                    gas_limit: None,
                     _ctx: PhantomData
                 }
             }
@@ -179,6 +198,12 @@ where
             pub fn address(&self) -> Address {
                 self.address
             }
+
+            // Caps the gas forwarded to each call
+            pub fn with_gas(mut self, gas_limit: u64) -> Self {
+                self.gas_limit = Some(gas_limit);
+                self
+            }
         }
 
         impl<C: StaticCtx> #interface_name<C> {
@@ -240,26 +265,46 @@ fn generate_method_impl(
         )
     };
 
+    // Interfaces built with a delegated context run the target's code within the caller's one
+    let call = quote! {
+        if C::DELEGATE {
+            eth_riscv_runtime::delegatecall_contract(
+                self.address,
+                &complete_calldata,
+                None,
+                self.gas_limit
+            )
+        } else {
+            #call_fn(
+                self.address,
+                alloy_core::primitives::U256::ZERO,
+                &complete_calldata,
+                None,
+                self.gas_limit
+            )
+        }
+    };
+
     // Generate different implementations based on return type
     match extract_wrapper_types(&method.return_type) {
         // If `Result<T, E>` handle each individual type
         WrapperType::Result(ok_type, err_type) => quote! {
-            pub fn #name(#self_param, #(#arg_names: #arg_types),*) -> Result<#ok_type, #err_type>  {
+            pub fn #name(
+                #self_param, #(#arg_names: #arg_types),*
+            ) -> Result<#ok_type, eth_riscv_runtime::CallError<#err_type>> {
                 use alloy_sol_types::SolValue;
                 use alloc::vec::Vec;
+                use eth_riscv_runtime::CallError;
 
                 #calldata
 
-                let result = #call_fn(
-                    self.address,
-                    0_u64,
-                    &complete_calldata,
-                    None
-                );
+                let result = #call;
 
-                match <#ok_type>::abi_decode(&result, true) {
-                    Ok(decoded) => Ok(decoded),
-                    Err(_) => Err(<#err_type>::abi_decode(&result, true))
+                // Data that can't be decoded is returned as is, rather than panicking
+                match result {
+                    Ok(output) => <#ok_type>::abi_decode(&output, true)
+                        .map_err(|_| CallError::InvalidData(output)),
+                    Err(revert_data) => Err(CallError::from_revert(revert_data))
                 }
             }
         },
@@ -272,15 +317,10 @@ fn generate_method_impl(
 
                     #calldata
 
-                    let result = #call_fn(
-                        self.address,
-                        0_u64,
-                        &complete_calldata,
-                        None
-                    );
+                    let result = #call;
 
-                    match <#return_ty>::abi_decode(&result, true) {
-                        Ok(decoded) => Some(decoded),
+                    match result {
+                        Ok(output) => <#return_ty>::abi_decode(&output, true).ok(),
                         Err(_) => None
                     }
                 }
@@ -299,15 +339,10 @@ fn generate_method_impl(
 
                     #calldata
 
-                    let result = #call_fn(
-                        self.address,
-                        0_u64,
-                        &complete_calldata,
-                        None
-                    );
+                    let result = #call;
 
-                    match <#return_ty>::abi_decode(&result, true) {
-                        Ok(decoded) => Some(decoded),
+                    match result {
+                        Ok(output) => <#return_ty>::abi_decode(&output, true).ok(),
                         Err(_) => None
                     }
                 }
@@ -388,9 +423,10 @@ pub fn generate_fn_selector(
     method: &MethodInfo,
     style: Option<InterfaceNamingStyle>,
 ) -> Option<[u8; 4]> {
-    let name = match style {
-        None => method.name.to_string(),
-        Some(style) => match style {
+    let name = match (method.sol_name, style) {
+        (Some(sol_name), _) => sol_name.to_string(),
+        (None, None) => method.name.to_string(),
+        (None, Some(style)) => match style {
             InterfaceNamingStyle::CamelCase => to_camel_case(method.name.to_string()),
         },
     };
@@ -412,108 +448,145 @@ pub fn generate_fn_selector(
     Some(selector_bytes)
 }
 
-// Helper function to convert rust types to their solidity equivalent
-// TODO: make sure that the impl is robust, so far only tested with "simple types"
-pub fn rust_type_to_sol_type(ty: &Type) -> Result<DynSolType, &'static str> {
+// Encoding of error, event and struct params: types known to `rust_type_to_sol_type` are
+// encoded as-is, tuples and arrays element-wise, and any other type must implement `SolStruct`
+enum SolParam<'a> {
+    Value(DynSolType),
+    Tuple(Vec<&'a Type>),
+    Vec(&'a Type),
+    Array(&'a Type, &'a syn::Expr),
+    Struct(&'a Type),
+}
+
+fn sol_param(ty: &Type) -> SolParam<'_> {
+    if let Ok(sol_type) = rust_type_to_sol_type(ty) {
+        return SolParam::Value(sol_type);
+    }
+
     match ty {
+        Type::Tuple(tuple) => SolParam::Tuple(tuple.elems.iter().collect()),
+        Type::Array(array) => SolParam::Array(&array.elem, &array.len),
         Type::Path(type_path) => {
-            let path = &type_path.path;
-            let segment = path.segments.last().ok_or("Empty type path")?;
-            let ident = &segment.ident;
-            let type_name = ident.to_string();
-
-            match type_name.as_str() {
-                // Fixed-size types
-                "Address" => Ok(DynSolType::Address),
-                "Function" => Ok(DynSolType::Function),
-                "bool" | "Bool" => Ok(DynSolType::Bool),
-                "String" | "str" => Ok(DynSolType::String),
-                "Bytes" => Ok(DynSolType::Bytes),
-                // Fixed-size bytes
-                b if b.starts_with('B') => {
-                    let size: usize = b
-                        .trim_start_matches('B')
-                        .parse()
-                        .map_err(|_| "Invalid fixed bytes size")?;
-                    if size > 0 && size <= 32 {
-                        Ok(DynSolType::FixedBytes(size))
-                    } else {
-                        Err("Invalid fixed bytes size (between 1-32)")
-                    }
-                }
-                // Fixed-size unsigned integers
-                u if u.starts_with('U') => {
-                    let size: usize = u
-                        .trim_start_matches('U')
-                        .parse()
-                        .map_err(|_| "Invalid uint size")?;
-                    if size > 0 && size <= 256 && size % 8 == 0 {
-                        Ok(DynSolType::Uint(size))
-                    } else {
-                        Err("Invalid uint size (multiple of 8 + leq 256)")
-                    }
-                }
-                // Fixed-size signed integers
-                i if i.starts_with('I') => {
-                    let size: usize = i
-                        .trim_start_matches('I')
-                        .parse()
-                        .map_err(|_| "Invalid int size")?;
-                    if size > 0 && size <= 256 && size % 8 == 0 {
-                        Ok(DynSolType::Int(size))
-                    } else {
-                        Err("Invalid int size (must be multiple of 8, max 256)")
-                    }
-                }
-                // Handle vecs
-                _ => {
-                    if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
-                        match type_name.as_str() {
-                            "Vec" => {
-                                let inner = args.args.first().ok_or("Empty Vec type argument")?;
-                                if let syn::GenericArgument::Type(inner_ty) = inner {
-                                    let inner_sol_type = rust_type_to_sol_type(inner_ty)?;
-                                    Ok(DynSolType::Array(Box::new(inner_sol_type)))
-                                } else {
-                                    Err("Invalid Vec type argument")
-                                }
-                            }
-                            _ => Err("Unsupported generic type"),
-                        }
-                    } else {
-                        Err("Unsupported type")
+            let segment = type_path.path.segments.last().expect("Empty type path");
+            match &segment.arguments {
+                PathArguments::None => SolParam::Struct(ty),
+                PathArguments::AngleBracketed(args) if segment.ident == "Vec" => {
+                    match args.args.first() {
+                        Some(syn::GenericArgument::Type(inner)) => SolParam::Vec(inner),
+                        _ => panic!("Invalid Vec type argument"),
                     }
                 }
+                _ => panic!(
+                    "Unsupported param type `{}`: expected a Solidity type, a `SolStruct`, or \
+                     a tuple, `Vec` or array of them",
+                    quote! { #ty }
+                ),
             }
         }
-        Type::Array(array) => {
-            let inner_sol_type = rust_type_to_sol_type(&array.elem)?;
-            if let syn::Expr::Lit(lit) = &array.len {
-                if let syn::Lit::Int(size) = &lit.lit {
-                    let size: usize = size
-                        .base10_digits()
-                        .parse()
-                        .map_err(|_| "Invalid array size")?;
-                    Ok(DynSolType::FixedArray(Box::new(inner_sol_type), size))
-                } else {
-                    Err("Invalid array size literal")
-                }
-            } else {
-                Err("Invalid array size expression")
-            }
+        _ => panic!("Unsupported param type `{}`", quote! { #ty }),
+    }
+}
+
+// Type used to ABI-encode a param
+pub fn sol_param_type(ty: &Type) -> TokenStream {
+    match sol_param(ty) {
+        SolParam::Value(_) => quote! { #ty },
+        SolParam::Tuple(elems) => {
+            let elems = elems.into_iter().map(sol_param_type);
+            quote! { (#(#elems,)*) }
+        }
+        SolParam::Vec(inner) => {
+            let inner = sol_param_type(inner);
+            quote! { alloc::vec::Vec<#inner> }
         }
-        Type::Tuple(tuple) => {
-            let inner_types = tuple
-                .elems
-                .iter()
-                .map(rust_type_to_sol_type)
-                .collect::<Result<Vec<_>, _>>()?;
-            Ok(DynSolType::Tuple(inner_types))
+        SolParam::Array(inner, len) => {
+            let inner = sol_param_type(inner);
+            quote! { [#inner; #len] }
         }
-        _ => Err("Unsupported type"),
+        SolParam::Struct(ty) => quote! { <#ty as eth_riscv_runtime::SolStruct>::Tuple },
     }
 }
 
+// Converts a param (place expression) into its ABI-encoding type
+pub fn sol_param_encode(ty: &Type, value: TokenStream) -> TokenStream {
+    match sol_param(ty) {
+        SolParam::Value(_) => quote! { #value.clone() },
+        SolParam::Tuple(elems) => {
+            let elems = elems.into_iter().enumerate().map(|(i, ty)| {
+                let index = syn::Index::from(i);
+                sol_param_encode(ty, quote! { #value.#index })
+            });
+            quote! { (#(#elems,)*) }
+        }
+        SolParam::Vec(inner) => {
+            let elem = sol_param_encode(inner, quote! { (*_v) });
+            quote! { #value.iter().map(|_v| #elem).collect::<alloc::vec::Vec<_>>() }
+        }
+        SolParam::Array(inner, _) => {
+            let elem = sol_param_encode(inner, quote! { (*_v) });
+            quote! { #value.each_ref().map(|_v| #elem) }
+        }
+        SolParam::Struct(_) => quote! { eth_riscv_runtime::SolStruct::to_tuple(&#value) },
+    }
+}
+
+// Converts a decoded value back into the param type
+pub fn sol_param_decode(ty: &Type, value: TokenStream) -> TokenStream {
+    match sol_param(ty) {
+        SolParam::Value(_) => value,
+        SolParam::Tuple(elems) => {
+            let vars: Vec<_> = (0..elems.len()).map(|i| format_ident!("_{}", i)).collect();
+            let elems = elems
+                .into_iter()
+                .zip(&vars)
+                .map(|(ty, var)| sol_param_decode(ty, quote! { #var }));
+            quote! { match #value { (#(#vars,)*) => (#(#elems,)*) } }
+        }
+        SolParam::Vec(inner) => {
+            let elem = sol_param_decode(inner, quote! { _v });
+            quote! { #value.into_iter().map(|_v| #elem).collect::<alloc::vec::Vec<_>>() }
+        }
+        SolParam::Array(inner, _) => {
+            let elem = sol_param_decode(inner, quote! { _v });
+            quote! { #value.map(|_v| #elem) }
+        }
+        SolParam::Struct(ty) => {
+            quote! { <#ty as eth_riscv_runtime::SolStruct>::from_tuple(#value) }
+        }
+    }
+}
+
+// Solidity type name of a param, as a `String` expression (struct names are resolved at runtime)
+pub fn sol_param_name(ty: &Type) -> TokenStream {
+    match sol_param(ty) {
+        SolParam::Value(sol_type) => {
+            let name = sol_type.sol_type_name().into_owned();
+            quote! { alloc::string::String::from(#name) }
+        }
+        SolParam::Tuple(elems) => sol_signature("", &elems),
+        SolParam::Vec(inner) => {
+            let inner = sol_param_name(inner);
+            quote! { alloc::format!("{}[]", #inner) }
+        }
+        SolParam::Array(inner, len) => {
+            let inner = sol_param_name(inner);
+            quote! { alloc::format!("{}[{}]", #inner, #len) }
+        }
+        SolParam::Struct(ty) => quote! { <#ty as eth_riscv_runtime::SolStruct>::sol_type_name() },
+    }
+}
+
+// Solidity signature (`name(type1,type2)`) of a list of params, as a `String` expression
+pub fn sol_signature(name: &str, types: &[&Type]) -> TokenStream {
+    if types.is_empty() {
+        let signature = format!("{}()", name);
+        return quote! { alloc::string::String::from(#signature) };
+    }
+
+    let names = types.iter().map(|ty| sol_param_name(ty));
+    quote! { alloc::format!("{}({})", #name, [#(#names),*].join(",")) }
+}
+
 fn to_camel_case(s: String) -> String {
     let mut result = String::new();
     let mut capitalize_next = false;
@@ -538,6 +611,45 @@ fn to_camel_case(s: String) -> String {
     result
 }
 
+// Helper function to generate the `StorageLayout` impl of a struct, whose fields are laid out
+// like Solidity state variables: small values are packed, the rest use their own slots
+pub fn generate_storage_layout(name: &Ident, fields: &[&syn::Field]) -> TokenStream {
+    let field_types: Vec<_> = fields.iter().map(|f| &f.ty).collect();
+    let n = fields.len();
+
+    let init_fields = fields.iter().enumerate().map(|(i, f)| {
+        let name = &f.ident;
+        quote! {
+            #name: StorageLayout::allocate(
+                slot + alloy_core::primitives::U256::from(positions[#i].0),
+                positions[#i].1 as u8,
+            )
+        }
+    });
+
+    quote! {
+        impl #name {
+            // `(slot, offset)` of each field (relative to the first slot), and the slots used
+            const __STORAGE_LAYOUT: ([(usize, usize); #n], usize) =
+                eth_riscv_runtime::types::compute_layout([#((
+                    <#field_types as eth_riscv_runtime::types::StorageLayout>::BYTES,
+                    <#field_types as eth_riscv_runtime::types::StorageLayout>::SLOTS,
+                )),*]);
+        }
+
+        impl eth_riscv_runtime::types::StorageLayout for #name {
+            const SLOTS: usize = Self::__STORAGE_LAYOUT.1;
+
+            fn allocate(slot: alloy_core::primitives::U256, _offset: u8) -> Self {
+                use eth_riscv_runtime::types::StorageLayout;
+
+                let (positions, _) = Self::__STORAGE_LAYOUT;
+                Self { #(#init_fields),* }
+            }
+        }
+    }
+}
+
 // Helper function to generate the deployment code
 pub fn generate_deployment_code(
     struct_name: &Ident,
@@ -635,147 +747,116 @@ mod tests {
     }
 
     #[test]
-    fn test_rust_to_sol_basic_types() {
-        let test_cases = vec![
-            (parse_quote!(Address), DynSolType::Address),
-            (parse_quote!(Function), DynSolType::Function),
-            (parse_quote!(bool), DynSolType::Bool),
-            (parse_quote!(Bool), DynSolType::Bool),
-            (parse_quote!(String), DynSolType::String),
-            (parse_quote!(str), DynSolType::String),
-            (parse_quote!(Bytes), DynSolType::Bytes),
-        ];
-
-        for (rust_type, expected_sol_type) in test_cases {
-            assert_eq!(
-                rust_type_to_sol_type(&rust_type).unwrap(),
-                expected_sol_type
-            );
-        }
-    }
-
-    #[test]
-    fn test_rust_to_sol_fixed_bytes() {
-        let test_cases = vec![
-            (parse_quote!(B1), DynSolType::FixedBytes(1)),
-            (parse_quote!(B16), DynSolType::FixedBytes(16)),
-            (parse_quote!(B32), DynSolType::FixedBytes(32)),
-        ];
-
-        for (rust_type, expected_sol_type) in test_cases {
-            assert_eq!(
-                rust_type_to_sol_type(&rust_type).unwrap(),
-                expected_sol_type
-            );
-        }
-
-        // Invalid cases
-        assert!(rust_type_to_sol_type(&parse_quote!(B0)).is_err());
-        assert!(rust_type_to_sol_type(&parse_quote!(B33)).is_err());
-    }
-
-    #[test]
-    fn test_rust_to_sol_integers() {
-        let test_cases = vec![
-            (parse_quote!(U8), DynSolType::Uint(8)),
-            (parse_quote!(U256), DynSolType::Uint(256)),
-            (parse_quote!(I8), DynSolType::Int(8)),
-            (parse_quote!(I256), DynSolType::Int(256)),
-        ];
-
-        for (rust_type, expected_sol_type) in test_cases {
-            assert_eq!(
-                rust_type_to_sol_type(&rust_type).unwrap(),
-                expected_sol_type
-            );
-        }
-
-        // Invalid cases
-        assert!(rust_type_to_sol_type(&parse_quote!(U0)).is_err());
-        assert!(rust_type_to_sol_type(&parse_quote!(U257)).is_err());
-        assert!(rust_type_to_sol_type(&parse_quote!(U7)).is_err()); // Not multiple of 8
-        assert!(rust_type_to_sol_type(&parse_quote!(I0)).is_err());
-        assert!(rust_type_to_sol_type(&parse_quote!(I257)).is_err());
-        assert!(rust_type_to_sol_type(&parse_quote!(I7)).is_err()); // Not multiple of 8
-    }
-
-    #[test]
-    fn test_rust_to_sol_arrays() {
-        // Dynamic arrays (Vec)
+    fn test_sol_params() {
+        // Known types are encoded as-is
+        let ty: Type = parse_quote!(U256);
+        assert_eq!(sol_param_type(&ty).to_string(), quote! { U256 }.to_string());
         assert_eq!(
-            rust_type_to_sol_type(&parse_quote!(Vec<U256>)).unwrap(),
-            DynSolType::Array(Box::new(DynSolType::Uint(256)))
+            sol_param_name(&ty).to_string(),
+            quote! { alloc::string::String::from("uint256") }.to_string()
         );
 
+        // Structs are encoded as their tuple, also when nested in a tuple
+        let ty: Type = parse_quote!((Address, Limit));
         assert_eq!(
-            rust_type_to_sol_type(&parse_quote!(Vec<Bool>)).unwrap(),
-            DynSolType::Array(Box::new(DynSolType::Bool))
+            sol_param_type(&ty).to_string(),
+            quote! { (Address, <Limit as eth_riscv_runtime::SolStruct>::Tuple,) }.to_string()
         );
-
-        // Fixed-size arrays
         assert_eq!(
-            rust_type_to_sol_type(&parse_quote!([U256; 5])).unwrap(),
-            DynSolType::FixedArray(Box::new(DynSolType::Uint(256)), 5)
+            sol_param_encode(&ty, quote! { self.value }).to_string(),
+            quote! {
+                (self.value.0.clone(), eth_riscv_runtime::SolStruct::to_tuple(&self.value.1),)
+            }
+            .to_string()
+        );
+        assert_eq!(
+            sol_param_decode(&ty, quote! { value }).to_string(),
+            quote! {
+                match value {
+                    (_0, _1,) => (_0, <Limit as eth_riscv_runtime::SolStruct>::from_tuple(_1),)
+                }
+            }
+            .to_string()
         );
-
         assert_eq!(
-            rust_type_to_sol_type(&parse_quote!([Bool; 3])).unwrap(),
-            DynSolType::FixedArray(Box::new(DynSolType::Bool), 3)
+            sol_signature("ExceedsLimit", &[&ty]).to_string(),
+            quote! {
+                alloc::format!("{}({})", "ExceedsLimit", [alloc::format!(
+                    "{}({})",
+                    "",
+                    [
+                        alloc::string::String::from("address"),
+                        <Limit as eth_riscv_runtime::SolStruct>::sol_type_name()
+                    ]
+                    .join(",")
+                )]
+                .join(","))
+            }
+            .to_string()
         );
     }
 
     #[test]
-    fn test_rust_to_sol_tuples() {
+    fn test_sol_struct_array_params() {
+        // Vecs and arrays of structs are encoded as arrays of their tuples
+        let ty: Type = parse_quote!(Vec<Limit>);
         assert_eq!(
-            rust_type_to_sol_type(&parse_quote!((U256, Bool))).unwrap(),
-            DynSolType::Tuple(vec![DynSolType::Uint(256), DynSolType::Bool])
+            sol_param_type(&ty).to_string(),
+            quote! { alloc::vec::Vec< <Limit as eth_riscv_runtime::SolStruct>::Tuple> }.to_string()
         );
-
         assert_eq!(
-            rust_type_to_sol_type(&parse_quote!((Address, B32, I128))).unwrap(),
-            DynSolType::Tuple(vec![
-                DynSolType::Address,
-                DynSolType::FixedBytes(32),
-                DynSolType::Int(128)
-            ])
+            sol_param_encode(&ty, quote! { self.limits }).to_string(),
+            quote! {
+                self.limits
+                    .iter()
+                    .map(|_v| eth_riscv_runtime::SolStruct::to_tuple(&(*_v)))
+                    .collect::<alloc::vec::Vec<_>>()
+            }
+            .to_string()
         );
-    }
-
-    #[test]
-    fn test_rust_to_sol_nested_types() {
-        // Nested Vec
         assert_eq!(
-            rust_type_to_sol_type(&parse_quote!(Vec<Vec<U256>>)).unwrap(),
-            DynSolType::Array(Box::new(DynSolType::Array(Box::new(DynSolType::Uint(256)))))
+            sol_param_decode(&ty, quote! { value }).to_string(),
+            quote! {
+                value
+                    .into_iter()
+                    .map(|_v| <Limit as eth_riscv_runtime::SolStruct>::from_tuple(_v))
+                    .collect::<alloc::vec::Vec<_>>()
+            }
+            .to_string()
         );
-
-        // Nested fixed array
         assert_eq!(
-            rust_type_to_sol_type(&parse_quote!([[U256; 2]; 3])).unwrap(),
-            DynSolType::FixedArray(
-                Box::new(DynSolType::FixedArray(Box::new(DynSolType::Uint(256)), 2)),
-                3
-            )
+            sol_param_name(&ty).to_string(),
+            quote! {
+                alloc::format!("{}[]", <Limit as eth_riscv_runtime::SolStruct>::sol_type_name())
+            }
+            .to_string()
         );
 
-        // Nested tuple
+        let ty: Type = parse_quote!([(U256, Limit); 2]);
         assert_eq!(
-            rust_type_to_sol_type(&parse_quote!((U256, (Bool, Address)))).unwrap(),
-            DynSolType::Tuple(vec![
-                DynSolType::Uint(256),
-                DynSolType::Tuple(vec![DynSolType::Bool, DynSolType::Address])
-            ])
+            sol_param_type(&ty).to_string(),
+            quote! { [(U256, <Limit as eth_riscv_runtime::SolStruct>::Tuple,); 2] }.to_string()
         );
+        assert_eq!(
+            sol_param_encode(&ty, quote! { self.pairs }).to_string(),
+            quote! {
+                self.pairs.each_ref().map(|_v| (
+                    (*_v).0.clone(),
+                    eth_riscv_runtime::SolStruct::to_tuple(&(*_v).1),
+                ))
+            }
+            .to_string()
+        );
+
+        // Arrays of known types are still encoded as-is
+        let ty: Type = parse_quote!(Vec<U256>);
+        assert_eq!(sol_param_type(&ty).to_string(), quote! { Vec<U256> }.to_string());
     }
 
     #[test]
-    fn test_rust_to_sol_invalid_types() {
-        // Invalid type names
-        assert!(rust_type_to_sol_type(&parse_quote!(InvalidType)).is_err());
-
-        // Invalid generic types
-        assert!(rust_type_to_sol_type(&parse_quote!(Option<U256>)).is_err());
-        assert!(rust_type_to_sol_type(&parse_quote!(Result<U256>)).is_err());
+    #[should_panic(expected = "Unsupported param type `Option < Limit >`")]
+    fn test_unsupported_generic_param() {
+        sol_param_type(&parse_quote!(Option<Limit>));
     }
 
     #[test]
@@ -1,5 +1,4 @@
 extern crate proc_macro;
-use alloy_core::primitives::U256;
 use alloy_sol_types::SolValue;
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
@@ -8,10 +7,12 @@ use syn::{
     ItemImpl, ItemTrait, ReturnType, TraitItem,
 };
 
+mod abi;
 mod helpers;
+use crate::abi::AbiInterfaceArgs;
 use crate::helpers::{InterfaceArgs, MethodInfo};
 
-#[proc_macro_derive(Error)]
+#[proc_macro_derive(Error, attributes(namespaced))]
 pub fn error_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
@@ -22,50 +23,55 @@ pub fn error_derive(input: TokenStream) -> TokenStream {
         panic!("`Error` must be an enum");
     };
 
-    // Generate error encoding for each variant
-    let encode_arms = variants.iter().map(|variant| {
-        let variant_name = &variant.ident;
-
-        let signature = match &variant.fields {
-            Fields::Unit => {
-                format!("{}::{}", name, variant_name)
-            }
-            Fields::Unnamed(fields) => {
-                let type_names: Vec<_> = fields
-                    .unnamed
-                    .iter()
-                    .map(|f| {
-                        helpers::rust_type_to_sol_type(&f.ty)
-                            .expect("Unknown type")
-                            .sol_type_name()
-                            .into_owned()
-                    })
-                    .collect();
-
-                format!("{}::{}({})", name, variant_name, type_names.join(","))
-            }
-            Fields::Named(_) => panic!("Named fields are not supported"),
-        };
+    // Errors use Solidity signatures (`InsufficientBalance(uint256)`), unless the enum opts
+    // into namespacing them with `#[namespaced]` (`ERC20Error::InsufficientBalance(uint256)`)
+    let namespace = if input.attrs.iter().any(|attr| attr.path.is_ident("namespaced")) {
+        format!("{}::", name)
+    } else {
+        String::new()
+    };
+    let signature = |variant: &syn::Variant| {
+        let types: Vec<_> = variant.fields.iter().map(|f| &f.ty).collect();
+        helpers::sol_signature(&format!("{}{}", namespace, variant.ident), &types)
+    };
 
-        let pattern = match &variant.fields {
+    // Fields are bound as `_0`, `_1`, ... (whether they are named or not)
+    let bindings = |variant: &syn::Variant| -> Vec<syn::Ident> {
+        (0..variant.fields.len()).map(|i| format_ident!("_{}", i)).collect()
+    };
+    // Variant pattern (or constructor), with the given value for each field
+    let variant_tokens = |variant: &syn::Variant, values: &[proc_macro2::TokenStream]| {
+        let variant_name = &variant.ident;
+        match &variant.fields {
             Fields::Unit => quote! { #name::#variant_name },
-            Fields::Unnamed(fields) => {
-                let vars: Vec<_> = (0..fields.unnamed.len())
-                    .map(|i| format_ident!("_{}", i))
-                    .collect();
-                quote! { #name::#variant_name(#(#vars),*) }
+            Fields::Unnamed(_) => quote! { #name::#variant_name(#(#values),*) },
+            Fields::Named(_) => {
+                let fields = variant.fields.iter().map(|f| &f.ident);
+                quote! { #name::#variant_name { #(#fields: #values),* } }
             }
-            Fields::Named(_) => panic!("Named fields are not supported"),
-        };
+        }
+    };
+    let pattern = |variant: &syn::Variant| {
+        let vars: Vec<_> = bindings(variant).into_iter().map(|v| quote! { #v }).collect();
+        variant_tokens(variant, &vars)
+    };
 
-        // non-unit variants must encode the data
-        let data = match &variant.fields {
-            Fields::Unit => quote! {},
-            Fields::Unnamed(fields) => {
-                let vars = (0..fields.unnamed.len()).map(|i| format_ident!("_{}", i));
-                quote! { #( res.extend_from_slice(&#vars.abi_encode()); )* }
-            }
-            Fields::Named(_) => panic!("Named fields are not supported"),
+    // Generate error encoding for each variant
+    let encode_arms = variants.iter().map(|variant| {
+        let signature = signature(variant);
+        let vars = bindings(variant);
+        let pattern = pattern(variant);
+
+        // non-unit variants must encode the data, as a tuple of all the fields
+        let data = if vars.is_empty() {
+            quote! {}
+        } else {
+            let values = variant
+                .fields
+                .iter()
+                .zip(&vars)
+                .map(|(f, var)| helpers::sol_param_encode(&f.ty, quote! { (*#var) }));
+            quote! { res.extend_from_slice(&(#(#values,)*).abi_encode_params()); }
         };
 
         quote! {
@@ -81,67 +87,58 @@ pub fn error_derive(input: TokenStream) -> TokenStream {
 
     // Generate error decoding for each variant
     let decode_arms = variants.iter().map(|variant| {
-        let variant_name = &variant.ident;
-        
-        let signature = match &variant.fields {
-            Fields::Unit => {
-                format!("{}::{}", name, variant_name)
-            },
-            Fields::Unnamed(fields) => {
-                let type_names: Vec<_> = fields.unnamed.iter()
-                    .map(|f| helpers::rust_type_to_sol_type(&f.ty)
-                        .expect("Unknown type")
-                        .sol_type_name()
-                        .into_owned()
-                    ).collect();
-                
-                format!("{}::{}({})",
-                    name,
-                    variant_name,
-                    type_names.join(",")
-                )
-            },
-            Fields::Named(_) => panic!("Named fields are not supported"),
-        };
+        let signature = signature(variant);
+        let selector_bytes = quote! { &keccak256(#signature.as_bytes())[..4] };
+        let vars = bindings(variant);
 
-        let selector_bytes = quote!{ &keccak256(#signature.as_bytes())[..4].to_vec() };
-
-        match &variant.fields {
-            Fields::Unit => quote! { selector if selector == #selector_bytes => #name::#variant_name },
-            Fields::Unnamed(fields) => {
-                let field_types: Vec<_> = fields.unnamed.iter().map(|f| &f.ty).collect();
-                let indices: Vec<_> = (0..fields.unnamed.len()).collect();
-                quote!{ selector if selector == #selector_bytes => {
-                    let mut values = Vec::new();
-                    #( values.push(<#field_types>::abi_decode(data.unwrap(), true).expect("Unable to decode")); )*
-                    #name::#variant_name(#(values[#indices]),*)
-                }} 
-            },
-            Fields::Named(_) => panic!("Named fields are not supported"),
+        if vars.is_empty() {
+            let constructor = variant_tokens(variant, &[]);
+            return quote! { selector if selector == #selector_bytes => Some(#constructor) };
         }
+
+        // All the fields are decoded at once, as a tuple
+        let types = variant.fields.iter().map(|f| helpers::sol_param_type(&f.ty));
+        let values: Vec<_> = variant
+            .fields
+            .iter()
+            .zip(&vars)
+            .map(|(f, var)| helpers::sol_param_decode(&f.ty, quote! { #var }))
+            .collect();
+        let constructor = variant_tokens(variant, &values);
+
+        quote! { selector if selector == #selector_bytes => {
+            let (#(#vars,)*) = <(#(#types,)*)>::abi_decode_params(&bytes[4..], validate).ok()?;
+            Some(#constructor)
+        }}
     });
 
     // Generate `Debug` implementation for each variant
     let debug_arms = variants.iter().map(|variant| {
         let variant_name = &variant.ident;
+        let vars = bindings(variant);
+        let pattern = pattern(variant);
 
         match &variant.fields {
             Fields::Unit => quote! {
-                #name::#variant_name => { f.write_str(stringify!(#variant_name)) }
+                #pattern => { f.write_str(stringify!(#variant_name)) }
             },
-            Fields::Unnamed(fields) => {
-                let vars: Vec<_> = (0..fields.unnamed.len())
-                    .map(|i| format_ident!("_{}", i))
-                    .collect();
+            Fields::Unnamed(_) => quote! {
+                #pattern => {
+                    f.debug_tuple(stringify!(#variant_name))
+                        #(.field(#vars))*
+                        .finish()
+                }
+            },
+            Fields::Named(_) => {
+                let fields = variant.fields.iter().map(|f| &f.ident);
                 quote! {
-                    #name::#variant_name(#(#vars),*) => {
-                        f.debug_tuple(stringify!(#variant_name))
-                            #(.field(#vars))*
+                    #pattern => {
+                        f.debug_struct(stringify!(#variant_name))
+                            #(.field(stringify!(#fields), #vars))*
                             .finish()
                     }
                 }
             }
-            Fields::Named(_) => panic!("Named fields are not supported"),
         }
     });
 
@@ -149,23 +146,22 @@ pub fn error_derive(input: TokenStream) -> TokenStream {
         impl eth_riscv_runtime::error::Error for #name {
             fn abi_encode(&self) -> alloc::vec::Vec<u8> {
                 use alloy_core::primitives::keccak256;
+                use alloy_sol_types::SolValue;
                 use alloc::vec::Vec;
 
                 match self { #(#encode_arms),* }
             }
 
-            fn abi_decode(bytes: &[u8], validate: bool) -> Self {
+            fn abi_decode(bytes: &[u8], validate: bool) -> Option<Self> {
                 use alloy_core::primitives::keccak256;
                 use alloy_sol_types::SolValue;
-                use alloc::vec::Vec;
 
-                if bytes.len() < 4 { panic!("Invalid error length") };
+                if bytes.len() < 4 { return None };
                 let selector = &bytes[..4];
-                let data = if bytes.len() > 4 { Some(&bytes[4..]) } else { None };
 
                 match selector {
                     #(#decode_arms),*,
-                    _ => panic!("Unknown error")
+                    _ => None
                 }
             }
         }
@@ -180,7 +176,7 @@ pub fn error_derive(input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
-#[proc_macro_derive(Event, attributes(indexed))]
+#[proc_macro_derive(Event, attributes(indexed, anonymous))]
 pub fn event_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
@@ -198,15 +194,39 @@ pub fn event_derive(input: TokenStream) -> TokenStream {
     // Collect iterators into vectors
     let field_names: Vec<_> = fields.iter().map(|f| &f.ident).collect();
     let field_types: Vec<_> = fields.iter().map(|f| &f.ty).collect();
-    let indexed_fields: Vec<_> = fields
-        .iter()
-        .filter(|f| f.attrs.iter().any(|attr| attr.path.is_ident("indexed")))
-        .map(|f| &f.ident)
-        .collect();
+    let is_indexed = |f: &syn::Field| f.attrs.iter().any(|attr| attr.path.is_ident("indexed"));
+    let indexed_fields: Vec<_> =
+        fields.iter().filter(|f| is_indexed(f)).map(|f| &f.ident).collect();
+
+    // Anonymous events don't emit their signature, leaving room for a 4th indexed field
+    let anonymous = input.attrs.iter().any(|attr| attr.path.is_ident("anonymous"));
+    let max_indexed = if anonymous { 4 } else { 3 };
+    if indexed_fields.len() > max_indexed {
+        panic!("Event `{}` can't have more than {} indexed fields", name, max_indexed);
+    }
+
+    // Indexed fields are emitted as topics, the rest is ABI-encoded (as a tuple) as data
+    let (topic_fields, data_fields): (Vec<_>, Vec<_>) = fields.iter().partition(|f| is_indexed(f));
+
+    let signature = helpers::sol_signature("", &field_types);
+    let topics = topic_fields.iter().map(|f| {
+        let field_name = &f.ident;
+        helpers::sol_param_encode(&f.ty, quote! { self.#field_name })
+    });
+    let data = if data_fields.is_empty() {
+        quote! { Vec::new() }
+    } else {
+        let values = data_fields.iter().map(|f| {
+            let field_name = &f.ident;
+            helpers::sol_param_encode(&f.ty, quote! { self.#field_name })
+        });
+        quote! { alloy_sol_types::SolValue::abi_encode_params(&(#(#values,)*)) }
+    };
 
     let expanded = quote! {
         impl #name {
             const NAME: &'static str = stringify!(#name);
+            const ANONYMOUS: bool = #anonymous;
             const INDEXED_FIELDS: &'static [&'static str] = &[
                 #(stringify!(#indexed_fields)),*
             ];
@@ -220,37 +240,72 @@ pub fn event_derive(input: TokenStream) -> TokenStream {
 
         impl eth_riscv_runtime::log::Event for #name {
             fn encode_log(&self) -> (alloc::vec::Vec<u8>, alloc::vec::Vec<[u8; 32]>) {
-                use alloy_sol_types::SolValue;
-                use alloy_core::primitives::{keccak256, B256};
+                use alloy_core::primitives::keccak256;
                 use alloc::vec::Vec;
 
-                let mut signature = Vec::new();
-                signature.extend_from_slice(Self::NAME.as_bytes());
-                signature.extend_from_slice(b"(");
+                // Topics are allocated before any temporary value, and data after them, so that
+                // `GLOBAL` can reclaim everything once the log is emitted
+                let mut topics = Vec::with_capacity(4);
+                if !Self::ANONYMOUS {
+                    let signature = alloc::format!("{}{}", Self::NAME, #signature);
+                    topics.push(keccak256(signature.as_bytes()).0);
+                }
+                #( topics.push(eth_riscv_runtime::log::encode_topic(&#topics).0); )*
 
-                let mut first = true;
-                let mut topics = alloc::vec![B256::default()];
-                let mut data = Vec::new();
+                let data = #data;
 
-                #(
-                    if !first { signature.extend_from_slice(b","); }
-                    first = false;
+                (data, topics)
+            }
+        }
+    };
 
-                    signature.extend_from_slice(self.#field_names.sol_type_name().as_bytes());
-                    let encoded = self.#field_names.abi_encode();
+    TokenStream::from(expanded)
+}
 
-                    let field_name = stringify!(#field_names);
-                    if Self::INDEXED_FIELDS.contains(&field_name) && topics.len() < 4 {
-                        topics.push(B256::from_slice(&encoded));
-                    } else {
-                        data.extend_from_slice(&encoded);
-                    }
-                )*
+// Structs used as error or event params, ABI-encoded as Solidity tuples
+#[proc_macro_derive(SolStruct)]
+pub fn sol_struct_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
 
-                signature.extend_from_slice(b")");
-                topics[0] = B256::from(keccak256(&signature));
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) if !fields.named.is_empty() => &fields.named,
+            _ => panic!("SolStruct must have named fields"),
+        },
+        _ => panic!("SolStruct must be a struct"),
+    };
+
+    let field_names: Vec<_> = fields.iter().map(|f| &f.ident).collect();
+    let field_types: Vec<_> = fields.iter().map(|f| &f.ty).collect();
+
+    let tuple_types = field_types.iter().map(|ty| helpers::sol_param_type(ty));
+    let signature = helpers::sol_signature("", &field_types);
+    let encoded = fields.iter().map(|f| {
+        let field_name = &f.ident;
+        helpers::sol_param_encode(&f.ty, quote! { self.#field_name })
+    });
+    let vars: Vec<_> = (0..fields.len()).map(|i| format_ident!("_{}", i)).collect();
+    let decoded = field_types
+        .iter()
+        .zip(&vars)
+        .map(|(ty, var)| helpers::sol_param_decode(ty, quote! { #var }));
+
+    let expanded = quote! {
+        impl eth_riscv_runtime::SolStruct for #name {
+            type Tuple = (#(#tuple_types,)*);
 
-                (data, topics.iter().map(|t| t.0).collect())
+            fn sol_type_name() -> alloc::string::String {
+                #signature
+            }
+
+            fn to_tuple(&self) -> Self::Tuple {
+                (#(#encoded,)*)
+            }
+
+            fn from_tuple(tuple: Self::Tuple) -> Self {
+                let (#(#vars,)*) = tuple;
+                Self { #(#field_names: #decoded),* }
             }
         }
     };
@@ -275,25 +330,43 @@ pub fn contract(_attr: TokenStream, item: TokenStream) -> TokenStream {
     };
 
     let mut constructor = None;
+    let mut receive = None;
+    let mut fallback = None;
     let mut public_methods: Vec<&ImplItemMethod> = Vec::new();
 
-    // Iterate over the items in the impl block to find pub methods + constructor
+    // Iterate over the items in the impl block to find pub methods + constructor + ETH handlers
     for item in input.items.iter() {
         if let ImplItem::Method(method) = item {
             if method.sig.ident == "new" {
                 constructor = Some(method);
+            } else if has_attribute(method, "receive") {
+                if receive.replace(method).is_some() {
+                    panic!("Only one `#[receive]` method is allowed");
+                }
+            } else if has_attribute(method, "fallback") {
+                if fallback.replace(method).is_some() {
+                    panic!("Only one `#[fallback]` method is allowed");
+                }
             } else if let syn::Visibility::Public(_) = method.vis {
                 public_methods.push(method);
             }
         }
     }
 
+    // ETH handlers are only reachable through the dispatcher, so they can't take arguments
+    for method in receive.iter().chain(fallback.iter()) {
+        if method.sig.inputs.len() != 1 {
+            panic!("`#[receive]` and `#[fallback]` methods can't take arguments");
+        }
+    }
+
     let input_methods: Vec<_> = public_methods
         .iter()
+        .chain(receive.iter())
+        .chain(fallback.iter())
         .map(|method| quote! { #method })
         .collect();
     let match_arms: Vec<_> = public_methods.iter().map(|method| {
-        let method_name = &method.sig.ident;
         let method_info = MethodInfo::from(*method);
         let method_selector = u32::from_be_bytes(
             helpers::generate_fn_selector(&method_info, None)
@@ -301,27 +374,19 @@ pub fn contract(_attr: TokenStream, item: TokenStream) -> TokenStream {
         );
         let (arg_names, arg_types) = helpers::get_arg_props_skip_first(&method_info);
 
-        // Check if there are payable methods
-        let checks = if !is_payable(&method) {
-            quote! {
-                if eth_riscv_runtime::msg_value() > U256::from(0) {
-                    panic!("Non-payable function");
-                }
-            }
-        } else {
-            quote! {}
-        };
+        let checks = value_checks(method);
+        let invocation = method_invocation(method, &arg_names);
 
         // Check if the method has a return type
         let return_handling = match &method.sig.output {
             ReturnType::Default => {
                 // No return value
-                quote! { self.#method_name(#( #arg_names ),*); }
+                quote! { #invocation; }
             }
            ReturnType::Type(_,_) => {
                 match helpers::extract_wrapper_types(&method.sig.output) {
                     helpers::WrapperType::Result(_,_) => quote! {
-                        let res = self.#method_name(#( #arg_names ),*);
+                        let res = #invocation;
                         match res {
                             Ok(success) => {
                                 let result_bytes = success.abi_encode();
@@ -335,7 +400,7 @@ pub fn contract(_attr: TokenStream, item: TokenStream) -> TokenStream {
                         }
                     },
                     helpers::WrapperType::Option(_) => quote! {
-                        match self.#method_name(#( #arg_names ),*) {
+                        match #invocation {
                             Some(success) => {
                                 let result_bytes = success.abi_encode();
                                 let result_size = result_bytes.len() as u64;
@@ -346,7 +411,7 @@ pub fn contract(_attr: TokenStream, item: TokenStream) -> TokenStream {
                         }
                     },
                     helpers::WrapperType::None => quote! {
-                        let result = self.#method_name(#( #arg_names ),*);
+                        let result = #invocation;
                         let result_bytes = result.abi_encode();
                         let result_size = result_bytes.len() as u64;
                         let result_ptr = result_bytes.as_ptr() as u64;
@@ -365,6 +430,31 @@ pub fn contract(_attr: TokenStream, item: TokenStream) -> TokenStream {
         }
     }).collect();
 
+    // Plain ETH transfers go to `receive`, falling back to `fallback` (as in Solidity)
+    let fallback_handling = match fallback {
+        Some(method) => {
+            let checks = value_checks(method);
+            let invocation = method_invocation(method, &[]);
+            quote! {
+                #checks
+                #invocation;
+                return_riscv(0, 0);
+            }
+        }
+        None => quote! { panic!("unknown method"); },
+    };
+    let receive_handling = match receive {
+        Some(method) => {
+            // `receive` is always payable
+            let invocation = method_invocation(method, &[]);
+            quote! {
+                #invocation;
+                return_riscv(0, 0);
+            }
+        }
+        None => fallback_handling.clone(),
+    };
+
     let emit_helper = quote! {
         #[macro_export]
         macro_rules! get_type_signature {
@@ -396,8 +486,8 @@ pub fn contract(_attr: TokenStream, item: TokenStream) -> TokenStream {
                     let encoded = $field.abi_encode();
 
                     let field_ident = stringify!($field);
-                    if $event::INDEXED_FIELDS.contains(&field_ident) && topics.len() < 4 {
-                        topics.push(B256::from_slice(&encoded));
+                    if $event::INDEXED_FIELDS.contains(&field_ident) && topics.len() < 5 {
+                        topics.push(eth_riscv_runtime::log::encode_topic(&$field));
                     } else {
                         data.extend_from_slice(&encoded);
                     }
@@ -405,6 +495,7 @@ pub fn contract(_attr: TokenStream, item: TokenStream) -> TokenStream {
 
                 signature.extend_from_slice(b")");
                 topics[0] = B256::from(keccak256(&signature));
+                if $event::ANONYMOUS { topics.remove(0); }
 
                 if !data.is_empty() {
                     eth_riscv_runtime::emit_log(&data, &topics);
@@ -470,12 +561,19 @@ pub fn contract(_attr: TokenStream, item: TokenStream) -> TokenStream {
                 }
 
                 fn call_with_data(&mut self, calldata: &[u8]) {
+                    if calldata.is_empty() {
+                        #receive_handling
+                    }
+                    if calldata.len() < 4 {
+                        #fallback_handling
+                    }
+
                     let selector = u32::from_be_bytes([calldata[0], calldata[1], calldata[2], calldata[3]]);
                     let calldata = &calldata[4..];
 
                     match selector {
                         #( #match_arms )*
-                        _ => panic!("unknown method"),
+                        _ => { #fallback_handling }
                     }
 
                     return_riscv(0, 0);
@@ -512,18 +610,82 @@ pub fn payable(_attr: TokenStream, item: TokenStream) -> TokenStream {
     item
 }
 
-// Check if a method is tagged with the payable attribute
-fn is_payable(method: &syn::ImplItemMethod) -> bool {
+// Empty macro to mark a method as the handler for plain ETH transfers
+#[proc_macro_attribute]
+pub fn receive(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    item
+}
+
+// Empty macro to mark a method as the handler for unknown selectors
+#[proc_macro_attribute]
+pub fn fallback(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    item
+}
+
+// Check if a method is tagged with the given attribute
+fn has_attribute(method: &syn::ImplItemMethod, name: &str) -> bool {
     method.attrs.iter().any(|attr| {
         if let Ok(syn::Meta::Path(path)) = attr.parse_meta() {
             if let Some(segment) = path.segments.first() {
-                return segment.ident == "payable";
+                return segment.ident == name;
             }
         }
         false
     })
 }
 
+// Empty macro to mark a method as nonreentrant
+#[proc_macro_attribute]
+pub fn nonreentrant(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    item
+}
+
+// Check if a method takes `&mut self`
+fn is_mutable(method: &syn::ImplItemMethod) -> bool {
+    matches!(
+        method.sig.inputs.first(),
+        Some(syn::FnArg::Receiver(receiver)) if receiver.mutability.is_some()
+    )
+}
+
+// Generate the checks that reject ETH transfers to non-payable methods
+fn value_checks(method: &syn::ImplItemMethod) -> proc_macro2::TokenStream {
+    if has_attribute(method, "payable") {
+        return quote! {};
+    }
+
+    quote! {
+        if eth_riscv_runtime::msg_value() > U256::from(0) {
+            panic!("Non-payable function");
+        }
+    }
+}
+
+// Generate the method call, wrapped with the reentrancy guard when required
+fn method_invocation(
+    method: &syn::ImplItemMethod,
+    arg_names: &[syn::Ident],
+) -> proc_macro2::TokenStream {
+    let method_name = &method.sig.ident;
+
+    if !has_attribute(method, "nonreentrant") {
+        quote! { self.#method_name(#( #arg_names ),*) }
+    } else if is_mutable(method) {
+        quote! {{
+            eth_riscv_runtime::reentrancy::enter();
+            let res = self.#method_name(#( #arg_names ),*);
+            eth_riscv_runtime::reentrancy::exit();
+            res
+        }}
+    } else {
+        // Read-only methods may run in a static context, so they only check the guard
+        quote! {{
+            eth_riscv_runtime::reentrancy::check();
+            self.#method_name(#( #arg_names ),*)
+        }}
+    }
+}
+
 #[proc_macro_attribute]
 pub fn interface(attr: TokenStream, item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as ItemTrait);
@@ -549,6 +711,54 @@ pub fn interface(attr: TokenStream, item: TokenStream) -> TokenStream {
     TokenStream::from(output)
 }
 
+// Generate an interface from a JSON ABI file (relative to the crate root), named after the file:
+// `interface_from_abi!("abi/IERC20.json")` or `interface_from_abi!(IToken, "abi/IERC20.json")`
+#[proc_macro]
+pub fn interface_from_abi(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as AbiInterfaceArgs);
+    let path = args.full_path();
+    let interface_name = args.interface_name();
+
+    let methods = match abi::parse_abi_methods(&path) {
+        Ok(methods) => methods,
+        Err(e) => {
+            return syn::Error::new(args.path.span(), e)
+                .to_compile_error()
+                .into()
+        }
+    };
+    let methods: Vec<_> = methods.iter().collect();
+
+    // Generate intreface implementation
+    let interface = helpers::generate_interface(&methods, &interface_name, None);
+    let path = path.to_string_lossy().to_string();
+    let output = quote! {
+        // Recompile whenever the ABI changes
+        const _: &[u8] = include_bytes!(#path);
+
+        #interface
+    };
+
+    TokenStream::from(output)
+}
+
+// Structs used as storage types (like `Mapping<Address, Position>`), laid out like `#[storage]`
+#[proc_macro_derive(StorageLayout)]
+pub fn storage_layout_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields.named.iter().collect::<Vec<_>>(),
+            _ => panic!("StorageLayout must have named fields"),
+        },
+        _ => panic!("StorageLayout must be a struct"),
+    };
+
+    TokenStream::from(helpers::generate_storage_layout(name, &fields))
+}
+
 #[proc_macro_attribute]
 pub fn storage(_attr: TokenStream, input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -576,23 +786,22 @@ pub fn storage(_attr: TokenStream, input: TokenStream) -> TokenStream {
         quote! { pub #name: #ty }
     });
 
-    // Generate initialization code for each field
-    // TODO: PoC uses a naive strategy. Enhance to support complex types like tuples or custom structs.
-    let init_fields = fields.iter().enumerate().map(|(i, f)| {
-        let name = &f.ident;
-        let slot = U256::from(i);
-        let [limb0, limb1, limb2, limb3] = slot.as_limbs();
-        quote! { #name: StorageLayout::allocate(#limb0, #limb1, #limb2, #limb3) }
-    });
+    // Fields are allocated following the storage layout, starting at slot 0
+    let layout = helpers::generate_storage_layout(name, &fields.iter().collect::<Vec<_>>());
 
     let expanded = quote! {
         #vis struct #name { #(#struct_fields,)* }
 
         impl #name {
             pub fn default() -> Self {
-                Self { #(#init_fields,)* }
+                eth_riscv_runtime::types::StorageLayout::allocate(
+                    alloy_core::primitives::U256::ZERO,
+                    0,
+                )
             }
         }
+
+        #layout
     };
 
     TokenStream::from(expanded)
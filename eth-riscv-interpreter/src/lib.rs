@@ -3,6 +3,9 @@ use rvemu::{bus::DRAM_BASE, dram::DRAM_SIZE, emulator::Emulator};
 mod error;
 pub use error::{Error, Result};
 
+mod memory;
+pub use memory::{memory_access, MemoryTracker, PAGE_SIZE};
+
 pub fn setup_from_elf(elf_data: &[u8], call_data: &[u8]) -> Result<Emulator> {
     let elf = goblin::elf::Elf::parse(elf_data)?;
 
@@ -0,0 +1,172 @@
+//! Page-granular accounting of the memory accessed by RISC-V programs
+
+use std::collections::BTreeSet;
+
+use rvemu::emulator::Emulator;
+
+/// Size of a memory page, in bytes
+pub const PAGE_SIZE: u64 = 4096;
+
+/// Index of the stack pointer register (`x2`)
+const SP: u64 = 2;
+
+/// Keeps track of the DRAM pages touched by loads and stores
+#[derive(Debug, Default)]
+pub struct MemoryTracker {
+    pages: BTreeSet<u64>,
+    highest_address: u64,
+}
+
+impl MemoryTracker {
+    /// Records an access of `size` bytes starting at `address`.
+    /// Returns the number of pages that had not been touched before.
+    pub fn touch(&mut self, address: u64, size: u64) -> u64 {
+        if size == 0 {
+            return 0;
+        }
+
+        let end = address.saturating_add(size - 1);
+        self.highest_address = self.highest_address.max(end);
+
+        (address / PAGE_SIZE..=end / PAGE_SIZE)
+            .filter(|page| self.pages.insert(*page))
+            .count() as u64
+    }
+
+    /// Number of distinct pages touched so far
+    pub fn touched_pages(&self) -> u64 {
+        self.pages.len() as u64
+    }
+
+    /// Highest address touched so far
+    pub fn highest_address(&self) -> u64 {
+        self.highest_address
+    }
+}
+
+/// Decodes the instruction at the current PC and returns the `(address, size)` of the memory
+/// it is about to access, or `None` if it doesn't access memory.
+pub fn memory_access(emu: &mut Emulator) -> Option<(u64, u64)> {
+    let pc = emu.cpu.pc;
+    let low = u16::from_le_bytes(read_bytes(emu, pc)?);
+    if low & 0b11 != 0b11 {
+        return compressed_memory_access(emu, low);
+    }
+
+    let inst = u32::from_le_bytes(read_bytes(emu, pc)?);
+    let funct3 = (inst >> 12) & 0x7;
+    let (offset, size) = match inst & 0x7f {
+        // LOAD: `imm[11:0]` at `inst[31:20]`
+        0x03 => (((inst as i32) >> 20) as i64, 1_u64 << (funct3 & 0b11)),
+        // STORE: `imm[11:5]` at `inst[31:25]`, `imm[4:0]` at `inst[11:7]`
+        0x23 => {
+            let imm = (((inst as i32) >> 25) << 5) | ((inst >> 7) & 0x1f) as i32;
+            (imm as i64, 1_u64 << (funct3 & 0b11))
+        }
+        // AMO: no offset, word or double-word
+        0x2f => (0, if funct3 == 0b010 { 4 } else { 8 }),
+        _ => return None,
+    };
+
+    let rs1 = ((inst >> 15) & 0x1f) as u64;
+    let address = emu.cpu.xregs.read(rs1).wrapping_add(offset as u64);
+    Some((address, size))
+}
+
+fn compressed_memory_access(emu: &mut Emulator, inst: u16) -> Option<(u64, u64)> {
+    let inst = inst as u64;
+    let bits = |hi: u64, lo: u64| (inst >> lo) & ((1_u64 << (hi - lo + 1)) - 1);
+
+    // Registers encoded in 3 bits map to `x8`-`x15`
+    let rs1_prime = bits(9, 7) + 8;
+
+    let (rs1, offset, size) = match (inst & 0b11, bits(15, 13)) {
+        // C.LW, C.SW
+        (0b00, 0b010 | 0b110) => (
+            rs1_prime,
+            (bits(12, 10) << 3) | (bits(6, 6) << 2) | (bits(5, 5) << 6),
+            4,
+        ),
+        // C.FLD, C.LD, C.FSD, C.SD
+        (0b00, 0b001 | 0b011 | 0b101 | 0b111) => {
+            (rs1_prime, (bits(12, 10) << 3) | (bits(6, 5) << 6), 8)
+        }
+        // C.LWSP
+        (0b10, 0b010) => (
+            SP,
+            (bits(12, 12) << 5) | (bits(6, 4) << 2) | (bits(3, 2) << 6),
+            4,
+        ),
+        // C.FLDSP, C.LDSP
+        (0b10, 0b001 | 0b011) => (
+            SP,
+            (bits(12, 12) << 5) | (bits(6, 5) << 3) | (bits(4, 2) << 6),
+            8,
+        ),
+        // C.SWSP
+        (0b10, 0b110) => (SP, (bits(12, 9) << 2) | (bits(8, 7) << 6), 4),
+        // C.FSDSP, C.SDSP
+        (0b10, 0b101 | 0b111) => (SP, (bits(12, 10) << 3) | (bits(9, 7) << 6), 8),
+        _ => return None,
+    };
+
+    let address = emu.cpu.xregs.read(rs1).wrapping_add(offset);
+    Some((address, size))
+}
+
+fn read_bytes<const N: usize>(emu: &mut Emulator, address: u64) -> Option<[u8; N]> {
+    let slice = emu
+        .cpu
+        .bus
+        .get_dram_slice(address..(address + N as u64))
+        .ok()?;
+    <[u8; N]>::try_from(&*slice).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use rvemu::bus::DRAM_BASE;
+
+    use super::*;
+
+    #[test]
+    fn test_touch_pages() {
+        let mut memory = MemoryTracker::default();
+
+        assert_eq!(memory.touch(DRAM_BASE, 8), 1);
+        assert_eq!(memory.touch(DRAM_BASE + 8, 8), 0);
+        // An access spanning two pages touches both of them
+        assert_eq!(memory.touch(DRAM_BASE + PAGE_SIZE - 4, 8), 1);
+        assert_eq!(memory.touch(DRAM_BASE, 0), 0);
+
+        assert_eq!(memory.touched_pages(), 2);
+        assert_eq!(memory.highest_address(), DRAM_BASE + PAGE_SIZE + 3);
+    }
+
+    #[test]
+    fn test_memory_access() {
+        let program: [(&[u8], Option<(u64, u64)>); 4] = [
+            // sd x1, 16(x2)
+            (&0x0011_3823_u32.to_le_bytes(), Some((0x100 + 16, 8))),
+            // lw x1, -4(x2)
+            (&0xffc1_2083_u32.to_le_bytes(), Some((0x100 - 4, 4))),
+            // c.sdsp ra, 8(sp)
+            (&0xe406_u16.to_le_bytes(), Some((0x100 + 8, 8))),
+            // addi x1, x0, 1
+            (&0x0010_0093_u32.to_le_bytes(), None),
+        ];
+
+        for (inst, expected) in program {
+            let mut mem = vec![0; 1024];
+            mem[..inst.len()].copy_from_slice(inst);
+
+            let mut emu = Emulator::new();
+            emu.initialize_dram(mem);
+            emu.initialize_pc(DRAM_BASE);
+            emu.cpu.xregs.write(SP, DRAM_BASE + 0x100);
+
+            let expected = expected.map(|(offset, size)| (DRAM_BASE + offset, size));
+            assert_eq!(memory_access(&mut emu), expected);
+        }
+    }
+}
@@ -0,0 +1,15 @@
+extern crate alloc;
+use alloc::string::String;
+use alloy_sol_types::SolValue;
+
+// Structs that are ABI-encoded as Solidity tuples (implemented by `#[derive(SolStruct)]`), so
+// that they can be used as error and event params
+pub trait SolStruct: Sized {
+    // Tuple of the (ABI-encodable) field values
+    type Tuple: SolValue;
+
+    // Tuple signature of the struct, i.e. `(uint256,address)`
+    fn sol_type_name() -> String;
+    fn to_tuple(&self) -> Self::Tuple;
+    fn from_tuple(tuple: Self::Tuple) -> Self;
+}
@@ -0,0 +1,114 @@
+extern crate alloc;
+use alloc::vec::Vec;
+use alloy_core::primitives::{Address, Bytes, B256, U256};
+use core::arch::asm;
+use eth_riscv_syscalls::Syscall;
+
+// Returns the address of the executing contract
+pub fn this() -> Address {
+    let first: u64;
+    let second: u64;
+    let third: u64;
+    unsafe {
+        asm!("ecall", lateout("a0") first, lateout("a1") second, lateout("a2") third, in("t0") u8::from(Syscall::Address));
+    }
+    let mut bytes = [0u8; 20];
+    bytes[0..8].copy_from_slice(&first.to_be_bytes());
+    bytes[8..16].copy_from_slice(&second.to_be_bytes());
+    bytes[16..20].copy_from_slice(&third.to_be_bytes()[..4]);
+    Address::from_slice(&bytes)
+}
+
+// Returns the balance of an account, in wei
+pub fn balance(addr: Address) -> U256 {
+    let addr: U256 = addr.into_word().into();
+    let addr = addr.as_limbs();
+    let (first, second, third, fourth): (u64, u64, u64, u64);
+    unsafe {
+        asm!(
+            "ecall",
+            lateout("a0") first, lateout("a1") second, lateout("a2") third, lateout("a3") fourth,
+            in("a0") addr[0], in("a1") addr[1], in("a2") addr[2],
+            in("t0") u8::from(Syscall::Balance)
+        );
+    }
+    U256::from_limbs([first, second, third, fourth])
+}
+
+// Returns the balance of the executing contract, in wei
+pub fn self_balance() -> U256 {
+    let (first, second, third, fourth): (u64, u64, u64, u64);
+    unsafe {
+        asm!("ecall", lateout("a0") first, lateout("a1") second, lateout("a2") third, lateout("a3") fourth, in("t0") u8::from(Syscall::SelfBalance));
+    }
+    U256::from_limbs([first, second, third, fourth])
+}
+
+// Returns the size of an account's code
+pub fn code_size(addr: Address) -> u64 {
+    let addr: U256 = addr.into_word().into();
+    let addr = addr.as_limbs();
+    let size: u64;
+    unsafe {
+        asm!(
+            "ecall",
+            lateout("a0") size,
+            in("a0") addr[0], in("a1") addr[1], in("a2") addr[2],
+            in("t0") u8::from(Syscall::ExtCodeSize)
+        );
+    }
+    size
+}
+
+// Returns the size of the executing code (the initcode, during construction)
+pub fn self_code_size() -> u64 {
+    let size: u64;
+    unsafe {
+        asm!("ecall", lateout("a0") size, in("t0") u8::from(Syscall::CodeSize));
+    }
+    size
+}
+
+// Returns the hash of an account's code, or zero if the account doesn't exist
+pub fn code_hash(addr: Address) -> B256 {
+    let addr: U256 = addr.into_word().into();
+    let addr = addr.as_limbs();
+    let (first, second, third, fourth): (u64, u64, u64, u64);
+    unsafe {
+        asm!(
+            "ecall",
+            lateout("a0") first, lateout("a1") second, lateout("a2") third, lateout("a3") fourth,
+            in("a0") addr[0], in("a1") addr[1], in("a2") addr[2],
+            in("t0") u8::from(Syscall::ExtCodeHash)
+        );
+    }
+    U256::from_limbs([first, second, third, fourth]).into()
+}
+
+// Copies `size` bytes of an account's code, starting at `offset` (zero-padded past its end)
+pub fn code_copy(addr: Address, offset: u64, size: u64) -> Bytes {
+    let mut code = Vec::with_capacity(size as usize);
+    code.resize(size as usize, 0);
+
+    let addr: U256 = addr.into_word().into();
+    let addr = addr.as_limbs();
+    unsafe {
+        asm!(
+            "ecall",
+            in("a0") addr[0], in("a1") addr[1], in("a2") addr[2],
+            in("a3") code.as_ptr() as u64, in("a4") offset, in("a5") size,
+            in("t0") u8::from(Syscall::ExtCodeCopy)
+        );
+    }
+    Bytes::from(code)
+}
+
+// Returns an account's code
+pub fn code(addr: Address) -> Bytes {
+    code_copy(addr, 0, code_size(addr))
+}
+
+// Whether an account has code (note that it is always false for contracts under construction)
+pub fn is_contract(addr: Address) -> bool {
+    code_size(addr) != 0
+}
@@ -1,6 +1,7 @@
 //! A very simple global allocator.
 //!
-//! Allocates on a global array and never deallocates.
+//! Allocates on a global array, as a stack: only the most recent allocation can be freed (or
+//! resized in place), which is enough to reclaim short-lived buffers like encoded keys.
 
 use core::{
     alloc::{GlobalAlloc, Layout},
@@ -23,14 +24,16 @@ impl<const SIZE: usize> FixedMemoryAllocator<SIZE> {
             next_available: Cell::new(0),
         }
     }
+
+    /// Whether the allocation ends where the next one would start.
+    fn is_last(&self, ptr: *mut u8, layout: Layout) -> bool {
+        let array_start = addr_of!(self.mem_buffer) as usize;
+        ptr as usize + layout.size() == array_start + self.next_available.get()
+    }
 }
 
 unsafe impl<const SIZE: usize> GlobalAlloc for FixedMemoryAllocator<SIZE> {
     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
-        self.alloc_zeroed(layout)
-    }
-
-    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
         // Start address of the allocation array:
         let array_start = addr_of!(self.mem_buffer) as usize;
 
@@ -54,8 +57,39 @@ unsafe impl<const SIZE: usize> GlobalAlloc for FixedMemoryAllocator<SIZE> {
         }
     }
 
-    unsafe fn dealloc(&self, _: *mut u8, _: Layout) {
-        // Do nothing. This allocator never deallocates.
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        // Freed memory can be handed out again, so it isn't zeroed anymore
+        let ptr = self.alloc(layout);
+        if !ptr.is_null() {
+            ptr::write_bytes(ptr, 0, layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // Only the last allocation can be freed, other ones are leaked
+        if self.is_last(ptr, layout) {
+            let array_start = addr_of!(self.mem_buffer) as usize;
+            self.next_available.set(ptr as usize - array_start);
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // The last allocation can be resized in place
+        let array_start = addr_of!(self.mem_buffer) as usize;
+        let new_next_available = ptr as usize + new_size - array_start;
+        if self.is_last(ptr, layout) && new_next_available <= SIZE {
+            self.next_available.set(new_next_available);
+            return ptr;
+        }
+
+        // Otherwise, move it to a new allocation
+        let new_layout = Layout::from_size_align_unchecked(new_size, layout.align());
+        let new_ptr = self.alloc(new_layout);
+        if !new_ptr.is_null() {
+            ptr::copy_nonoverlapping(ptr, new_ptr, layout.size().min(new_size));
+        }
+        new_ptr
     }
 }
 
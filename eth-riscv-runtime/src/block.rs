@@ -1,4 +1,4 @@
-use alloy_core::primitives::U256;
+use alloy_core::primitives::{Address, B256, U256};
 use eth_riscv_syscalls::Syscall;
 use core::arch::asm;
 
@@ -57,4 +57,55 @@ pub fn number() -> U256 {
         asm!("ecall", lateout("a0") first, lateout("a1") second, lateout("a2") third, lateout("a3") fourth, in("t0") u8::from(Syscall::Number));
     }
     U256::from_limbs([first, second, third, fourth])
-}
\ No newline at end of file
+}
+
+// Returns the hash of one of the 256 most recent blocks, or zero for any other block
+pub fn block_hash(number: u64) -> B256 {
+    let first: u64;
+    let second: u64;
+    let third: u64;
+    let fourth: u64;
+    unsafe {
+        asm!("ecall", inlateout("a0") number => first, lateout("a1") second, lateout("a2") third, lateout("a3") fourth, in("t0") u8::from(Syscall::BlockHash));
+    }
+    U256::from_limbs([first, second, third, fourth]).into()
+}
+
+// Returns current block beneficiary
+pub fn coinbase() -> Address {
+    let first: u64;
+    let second: u64;
+    let third: u64;
+    unsafe {
+        asm!("ecall", lateout("a0") first, lateout("a1") second, lateout("a2") third, in("t0") u8::from(Syscall::Coinbase));
+    }
+    let mut bytes = [0u8; 20];
+    bytes[0..8].copy_from_slice(&first.to_be_bytes());
+    bytes[8..16].copy_from_slice(&second.to_be_bytes());
+    bytes[16..20].copy_from_slice(&third.to_be_bytes()[..4]);
+    Address::from_slice(&bytes)
+}
+
+// Returns the previous block's RANDAO mix (EIP-4399)
+pub fn prev_randao() -> B256 {
+    let first: u64;
+    let second: u64;
+    let third: u64;
+    let fourth: u64;
+    unsafe {
+        asm!("ecall", lateout("a0") first, lateout("a1") second, lateout("a2") third, lateout("a3") fourth, in("t0") u8::from(Syscall::PrevRandao));
+    }
+    U256::from_limbs([first, second, third, fourth]).into()
+}
+
+// Returns current block blob base fee (EIP-7516)
+pub fn blob_base_fee() -> U256 {
+    let first: u64;
+    let second: u64;
+    let third: u64;
+    let fourth: u64;
+    unsafe {
+        asm!("ecall", lateout("a0") first, lateout("a1") second, lateout("a2") third, lateout("a3") fourth, in("t0") u8::from(Syscall::BlobBaseFee));
+    }
+    U256::from_limbs([first, second, third, fourth])
+}
@@ -0,0 +1,69 @@
+//! Opt-in write-back cache for storage slots, enabled by the `storage-cache` feature.
+//!
+//! Each slot is loaded at most once per call, and writes are kept in memory until the call
+//! returns, so that hot paths (like reading and updating the same balance) pay for every slot
+//! once. Dirty slots are flushed:
+//! - when the call returns (`return_riscv`) or self-destructs.
+//! - before calling (or deploying) other contracts, which could re-enter this one.
+//!
+//! Reverts discard the cache, as the writes of a reverted call never reach the storage.
+
+use alloy_core::primitives::U256;
+use ext_alloc::collections::BTreeMap;
+
+use crate::{sys_sload, sys_sstore};
+
+struct CachedSlot {
+    value: U256,
+    dirty: bool,
+}
+
+// Contracts are single-threaded, and each call has its own memory
+static mut CACHE: BTreeMap<U256, CachedSlot> = BTreeMap::new();
+
+#[allow(static_mut_refs)]
+fn cache() -> &'static mut BTreeMap<U256, CachedSlot> {
+    unsafe { &mut CACHE }
+}
+
+/// Reads a slot, only loading it from storage (`SLOAD`) if it isn't cached yet.
+pub fn sload(key: U256) -> U256 {
+    cache()
+        .entry(key)
+        .or_insert_with(|| CachedSlot { value: sys_sload(key), dirty: false })
+        .value
+}
+
+/// Writes a slot to the cache, deferring the `SSTORE` until it is flushed.
+pub fn sstore(key: U256, value: U256) {
+    cache().insert(key, CachedSlot { value, dirty: true });
+}
+
+/// Writes the dirty slots to storage. Cached values remain valid.
+pub fn flush() {
+    for (key, slot) in cache().iter_mut().filter(|(_, slot)| slot.dirty) {
+        sys_sstore(*key, slot.value);
+        slot.dirty = false;
+    }
+}
+
+/// Discards the cached slots, without writing them to storage.
+pub fn clear() {
+    cache().clear();
+}
+
+/// Flushes the dirty slots before an external call, and discards the cache afterwards
+/// (unless the call is static), as a re-entrant call could have modified the storage.
+pub(crate) fn around_call<T>(is_static: bool, call: impl FnOnce() -> T) -> T {
+    if !cfg!(feature = "storage-cache") {
+        return call();
+    }
+
+    flush();
+    let result = call();
+    if !is_static {
+        clear();
+    }
+
+    result
+}
@@ -7,17 +7,24 @@ use eth_riscv_syscalls::Syscall;
 // Concrete types implementing the context traits
 pub struct ReadOnly;
 pub struct ReadWrite;
+pub struct Delegated;
 
 // Marker traits to determine call context
-pub trait CallCtx {}
+pub trait CallCtx {
+    // Whether calls are issued as delegate calls
+    const DELEGATE: bool = false;
+}
 pub trait StaticCtx: CallCtx {}
 pub trait MutableCtx: StaticCtx {}
 
 impl CallCtx for ReadOnly {}
 impl CallCtx for ReadWrite {}
+impl CallCtx for Delegated { const DELEGATE: bool = true; }
 impl StaticCtx for ReadOnly {}
 impl StaticCtx for ReadWrite {}
+impl StaticCtx for Delegated {}
 impl MutableCtx for ReadWrite {}
+impl MutableCtx for Delegated {}
 
 // Marker trait to connect contract method context with call ctx
 pub trait MethodCtx { type Allowed: CallCtx; }
@@ -55,6 +62,23 @@ impl<I> InterfaceBuilder<I> {
         };
         T::from_builder(target_builder)
     }
+
+    // Delegate calls can modify the caller's storage, so they require a mutable context
+    pub fn with_delegate_ctx<M: MethodCtx, T>(
+        self,
+        _: M
+    ) -> T 
+    where
+        I: IntoInterface<T>,
+        M: MethodCtx<Allowed = ReadWrite>,
+        T: FromBuilder<Context = Delegated>
+    {
+        let target_builder = InterfaceBuilder {
+            address: self.address,
+            _phantom: PhantomData,
+        };
+        T::from_builder(target_builder)
+    }
 }
 
 pub trait FromBuilder: Sized {
@@ -69,39 +93,77 @@ pub trait Contract {
     fn call_with_data(&mut self, calldata: &[u8]);
 }
 
+// Gas limit that forwards all the available gas (minus the 1/64 retained by the caller)
+pub const ALL_GAS: u64 = u64::MAX;
+
 pub fn call_contract(
     addr: Address,
-    value: u64,
+    value: U256,
     data: &[u8],
     ret_size: Option<u64>,
-) -> Bytes {
+    gas_limit: Option<u64>,
+) -> Result<Bytes, Bytes> {
     // Perform the call without writing return data into (REVM) memory
-    call(addr, value, data.as_ptr() as u64, data.len() as u64);
+    let success = call(addr, value, data.as_ptr() as u64, data.len() as u64, gas_limit.unwrap_or(ALL_GAS));
     // Load call output to memory
-    handle_call_output(ret_size)
+    handle_call_result(success, ret_size)
 }
 
-pub fn call(addr: Address, value: u64, data_offset: u64, data_size: u64) {
+pub fn call(addr: Address, value: U256, data_offset: u64, data_size: u64, gas_limit: u64) -> bool {
     let addr: U256 = addr.into_word().into();
     let addr = addr.as_limbs();
-    unsafe {
-        asm!(
-            "ecall",
-            in("a0") addr[0], in("a1") addr[1], in("a2") addr[2],
-            in("a3") value, in("a4") data_offset, in("a5") data_size,
-            in("t0") u8::from(Syscall::Call)
-        );
-    }
+    let value = value.to_be_bytes::<32>();
+    let success = crate::cache::around_call(false, || {
+        let success: u64;
+        unsafe {
+            asm!(
+                "ecall",
+                lateout("a0") success,
+                in("a0") addr[0], in("a1") addr[1], in("a2") addr[2],
+                in("a3") value.as_ptr() as u64, in("a4") data_offset, in("a5") data_size,
+                in("a6") gas_limit, in("t0") u8::from(Syscall::Call)
+            );
+        }
+        success
+    });
+    success != 0
 }
 
-pub fn staticcall_contract(addr: Address, value: u64, data: &[u8], ret_size: Option<u64>) -> Bytes {
+pub fn staticcall_contract(
+    addr: Address,
+    value: U256,
+    data: &[u8],
+    ret_size: Option<u64>,
+    gas_limit: Option<u64>,
+) -> Result<Bytes, Bytes> {
     // Perform the staticcall without writing return data into (REVM) memory
-    staticcall(addr, value, data.as_ptr() as u64, data.len() as u64);
+    let success = staticcall(addr, value, data.as_ptr() as u64, data.len() as u64, gas_limit.unwrap_or(ALL_GAS));
+    // Load call output to memory
+    handle_call_result(success, ret_size)
+}
+
+pub fn delegatecall_contract(
+    addr: Address,
+    data: &[u8],
+    ret_size: Option<u64>,
+    gas_limit: Option<u64>,
+) -> Result<Bytes, Bytes> {
+    // Perform the delegatecall without writing return data into (REVM) memory
+    let success = delegatecall(addr, data.as_ptr() as u64, data.len() as u64, gas_limit.unwrap_or(ALL_GAS));
     // Load call output to memory
-    handle_call_output(ret_size)
+    handle_call_result(success, ret_size)
+}
+
+// Returns the call output on success, or the revert data (of any size) on failure
+fn handle_call_result(success: bool, ret_size: Option<u64>) -> Result<Bytes, Bytes> {
+    if success {
+        Ok(handle_call_output(ret_size))
+    } else {
+        Err(handle_call_output(None))
+    }
 }
 
-fn handle_call_output(ret_size: Option<u64>) -> Bytes {
+pub(crate) fn handle_call_output(ret_size: Option<u64>) -> Bytes {
     // Figure out return data size + initialize memory location
     let ret_size = match ret_size {
         Some(size) => size,
@@ -133,17 +195,43 @@ fn handle_call_output(ret_size: Option<u64>) -> Bytes {
     Bytes::from(ret_data)
 }
 
-pub fn staticcall(addr: Address, value: u64, data_offset: u64, data_size: u64) {
+pub fn staticcall(addr: Address, value: U256, data_offset: u64, data_size: u64, gas_limit: u64) -> bool {
     let addr: U256 = addr.into_word().into();
     let addr = addr.as_limbs();
-    unsafe {
-        asm!(
-            "ecall",
-            in("a0") addr[0], in("a1") addr[1], in("a2") addr[2],
-            in("a3") value, in("a4") data_offset, in("a5") data_size,
-            in("t0") u8::from(Syscall::StaticCall)
-        );
-    }
+    let value = value.to_be_bytes::<32>();
+    let success = crate::cache::around_call(true, || {
+        let success: u64;
+        unsafe {
+            asm!(
+                "ecall",
+                lateout("a0") success,
+                in("a0") addr[0], in("a1") addr[1], in("a2") addr[2],
+                in("a3") value.as_ptr() as u64, in("a4") data_offset, in("a5") data_size,
+                in("a6") gas_limit, in("t0") u8::from(Syscall::StaticCall)
+            );
+        }
+        success
+    });
+    success != 0
+}
+
+pub fn delegatecall(addr: Address, data_offset: u64, data_size: u64, gas_limit: u64) -> bool {
+    let addr: U256 = addr.into_word().into();
+    let addr = addr.as_limbs();
+    let success = crate::cache::around_call(false, || {
+        let success: u64;
+        unsafe {
+            asm!(
+                "ecall",
+                lateout("a0") success,
+                in("a0") addr[0], in("a1") addr[1], in("a2") addr[2],
+                in("a3") data_offset, in("a4") data_size,
+                in("a5") gas_limit, in("t0") u8::from(Syscall::DelegateCall)
+            );
+        }
+        success
+    });
+    success != 0
 }
 
 pub fn return_data_size() -> u64 {
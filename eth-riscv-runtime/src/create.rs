@@ -1,11 +1,11 @@
 extern crate alloc;
-use alloy_core::primitives::{Address, Bytes, U32};
+use alloy_core::primitives::{Address, Bytes, B256, U256, U32};
 use alloy_sol_types::{SolType, SolValue};
 use ext_alloc::vec::Vec;
 use core::{arch::asm, marker::PhantomData, u64};
 use eth_riscv_syscalls::Syscall;
 
-use crate::{FromBuilder, InitInterface, MethodCtx, ReadWrite};
+use crate::{call::handle_call_output, CallError, Error, ALL_GAS, FromBuilder, InitInterface, MethodCtx, ReadWrite};
 
 pub trait Deployable {
     type Interface: InitInterface;
@@ -26,6 +26,9 @@ pub trait Deployable {
     {
         DeploymentBuilder {
             args,
+            value: U256::ZERO,
+            salt: None,
+            gas_limit: ALL_GAS,
             _phantom: PhantomData,
         }
     } 
@@ -36,6 +39,9 @@ where
     Args: SolValue + core::convert::From<<<Args as SolValue>::SolType as SolType>::RustType>
 {
     args: Args,
+    value: U256,
+    salt: Option<B256>,
+    gas_limit: u64,
     _phantom: PhantomData<D>,
 }
 
@@ -44,9 +50,28 @@ where
     Args: SolValue + core::convert::From<<<Args as SolValue>::SolType as SolType>::RustType>
 {
 
-    // Return the interface with the appropriate context
-    pub fn with_ctx<M, T>(self, ctx: M) -> T 
+    // Sets the value (in wei) sent to the deployed contract
+    pub fn with_value(mut self, value: U256) -> Self {
+        self.value = value;
+        self
+    }
+
+    // Deploys with CREATE2, so that the address only depends on the salt and initcode
+    pub fn with_salt(mut self, salt: B256) -> Self {
+        self.salt = Some(salt);
+        self
+    }
+
+    // Caps the gas forwarded to the constructor
+    pub fn with_gas(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = gas_limit;
+        self
+    }
+
+    // Return the interface with the appropriate context, or the constructor error if the deployment failed
+    pub fn with_ctx<M, T, E>(self, ctx: M) -> Result<T, CallError<E>>
     where
+        E: Error,
         M: MethodCtx<Allowed = ReadWrite>, // Constrain to mutable contexts only
         D::Interface: InitInterface,
         T: FromBuilder<Context = M::Allowed>,
@@ -67,13 +92,19 @@ where
         let offset = init_code.as_ptr() as u64;
         let size = init_code.len() as u64;
 
-        // TODO: think of an ergonomic API to handle deployments with values
-        create(0, offset, size);
+        // The constructor could call back into this contract, so storage must be up-to-date
+        crate::cache::around_call(false, || match self.salt {
+            Some(salt) => create2(self.value, offset, size, salt, self.gas_limit),
+            None => create(self.value, offset, size, self.gas_limit),
+        });
 
         // Get deployment address
         let mut ret_data = Vec::with_capacity(20);
         ret_data.resize(20 as usize, 0);
-        return_create_address(ret_data.as_ptr() as u64);
+        if !return_create_address(ret_data.as_ptr() as u64) {
+            // Decode the constructor revert data
+            return Err(CallError::from_revert(handle_call_output(None)));
+        }
 
         let address = Address::from_slice(&ret_data);
         
@@ -81,23 +112,37 @@ where
         let builder = D::Interface::new(address);
         
         // Convert to the actual interface with context
-        builder.with_ctx(ctx)
+        Ok(builder.with_ctx(ctx))
+    }
+}
+
+fn create(value: U256, data_offset: u64, data_size: u64, gas_limit: u64) {
+    let value = value.to_be_bytes::<32>();
+    unsafe {
+        asm!(
+            "ecall",
+            in("a0") value.as_ptr() as u64, in("a1") data_offset, in("a2") data_size,
+            in("a3") gas_limit, in("t0") u8::from(Syscall::Create)
+        );
     }
 }
 
-fn create(value: u64, data_offset: u64, data_size: u64) {
+fn create2(value: U256, data_offset: u64, data_size: u64, salt: B256, gas_limit: u64) {
+    let value = value.to_be_bytes::<32>();
     unsafe {
         asm!(
             "ecall",
-            in("a0") value, in("a1") data_offset, in("a2") data_size,
-            in("t0") u8::from(Syscall::Create)
+            in("a0") value.as_ptr() as u64, in("a1") data_offset, in("a2") data_size,
+            in("a3") salt.as_ptr() as u64, in("a4") gas_limit, in("t0") u8::from(Syscall::Create2)
         );
     }
 }
 
-fn return_create_address(data_offset: u64) {
+fn return_create_address(data_offset: u64) -> bool {
+    let status: u64;
     unsafe {
         asm!(
-            "ecall", in("a0") data_offset, in("t0") u8::from(Syscall::ReturnCreateAddress));
+            "ecall", inlateout("a0") data_offset => status, in("t0") u8::from(Syscall::ReturnCreateAddress));
     }
+    status != 0
 }
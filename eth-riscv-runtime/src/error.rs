@@ -1,15 +1,73 @@
 extern crate alloc;
 use alloc::vec::Vec;
-use core::arch::asm;
+use alloy_core::primitives::Bytes;
+use core::{arch::asm, fmt::{Debug, Formatter}};
 use crate::Syscall;
 
-pub trait Error {
+pub trait Error: Sized {
     fn abi_encode(&self) -> Vec<u8>;
-    fn abi_decode(bytes: &[u8], validate: bool) -> Self;
+    /// Decodes the error, or returns `None` if the data doesn't match any of its variants.
+    fn abi_decode(bytes: &[u8], validate: bool) -> Option<Self>;
+}
+
+/// Error of a call (or deployment) made through a contract interface.
+pub enum CallError<E> {
+    /// The target reverted with one of its errors.
+    Revert(E),
+    /// The target returned (or reverted with) data that couldn't be decoded.
+    InvalidData(Bytes),
+}
+
+impl<E: Error> CallError<E> {
+    /// Decodes the revert data of a failed call.
+    pub fn from_revert(data: Bytes) -> Self {
+        match E::abi_decode(&data, true) {
+            Some(err) => Self::Revert(err),
+            None => Self::InvalidData(data),
+        }
+    }
+}
+
+// Reverts are formatted as the target's error itself
+impl<E: Debug> Debug for CallError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Revert(err) => err.fmt(f),
+            Self::InvalidData(data) => f.debug_tuple("InvalidData").field(data).finish(),
+        }
+    }
+}
+
+impl<E> From<E> for CallError<E> {
+    fn from(err: E) -> Self {
+        Self::Revert(err)
+    }
+}
+
+// Methods can return call errors, which revert with the original data
+impl<E: Error> Error for CallError<E> {
+    fn abi_encode(&self) -> Vec<u8> {
+        match self {
+            Self::Revert(err) => err.abi_encode(),
+            Self::InvalidData(data) => data.to_vec(),
+        }
+    }
+
+    fn abi_decode(bytes: &[u8], validate: bool) -> Option<Self> {
+        match E::abi_decode(bytes, validate) {
+            Some(err) => Some(Self::Revert(err)),
+            None => Some(Self::InvalidData(Bytes::copy_from_slice(bytes))),
+        }
+    }
 }
 
 pub fn revert() -> ! { revert_with_error(Vec::new().as_slice()) }
 pub fn revert_with_error(data: &[u8]) -> ! {
+    // Cached writes are discarded, like the rest of the state changes of the call
+    if cfg!(feature = "storage-cache") {
+        crate::cache::clear();
+    }
+
     let (offset, size) = (data.as_ptr() as u64, data.len() as u64);
     unsafe {
         asm!("ecall",
@@ -8,15 +8,20 @@ pub use riscv_rt::entry;
 extern crate alloc as ext_alloc;
 
 mod alloc;
+pub mod account;
 pub mod block;
+pub mod reentrancy;
 pub mod tx;
 pub mod types;
 
 pub mod create;
 pub use create::Deployable;
 
+pub mod abi;
+pub use abi::SolStruct;
+
 pub mod error;
-pub use error::{revert, revert_with_error, Error};
+pub use error::{revert, revert_with_error, CallError, Error};
 
 pub mod log;
 pub use log::{emit_log, Event};
@@ -24,6 +29,8 @@ pub use log::{emit_log, Event};
 pub mod call;
 pub use call::*;
 
+pub mod cache;
+
 const CALLDATA_ADDRESS: usize = 0x8000_0000;
 
 pub unsafe fn slice_from_raw_parts(address: usize, length: usize) -> &'static [u8] {
@@ -52,13 +59,53 @@ unsafe fn panic(info: &PanicInfo<'_>) -> ! {
 use eth_riscv_syscalls::Syscall;
 
 pub fn return_riscv(addr: u64, offset: u64) -> ! {
+    if cfg!(feature = "storage-cache") {
+        cache::flush();
+    }
+
     unsafe {
         asm!("ecall", in("a0") addr, in("a1") offset, in("t0") u8::from(Syscall::Return));
     }
     unreachable!()
 }
 
+// Sends the whole balance to the beneficiary, deleting the contract if created in the same tx
+pub fn selfdestruct(beneficiary: Address) -> ! {
+    if cfg!(feature = "storage-cache") {
+        cache::flush();
+    }
+
+    let beneficiary: U256 = beneficiary.into_word().into();
+    let beneficiary = beneficiary.as_limbs();
+    unsafe {
+        asm!(
+            "ecall",
+            in("a0") beneficiary[0], in("a1") beneficiary[1], in("a2") beneficiary[2],
+            in("t0") u8::from(Syscall::SelfDestruct)
+        );
+    }
+    unreachable!()
+}
+
+// With the `storage-cache` feature, each slot is only loaded once per call
 pub fn sload(key: U256) -> U256 {
+    if cfg!(feature = "storage-cache") {
+        cache::sload(key)
+    } else {
+        sys_sload(key)
+    }
+}
+
+// With the `storage-cache` feature, writes are deferred until the call returns
+pub fn sstore(key: U256, value: U256) {
+    if cfg!(feature = "storage-cache") {
+        cache::sstore(key, value)
+    } else {
+        sys_sstore(key, value)
+    }
+}
+
+pub(crate) fn sys_sload(key: U256) -> U256 {
     let key = key.as_limbs();
     let (val0, val1, val2, val3): (u64, u64, u64, u64);
     unsafe {
@@ -71,7 +118,7 @@ pub fn sload(key: U256) -> U256 {
     U256::from_limbs([val0, val1, val2, val3])
 }
 
-pub fn sstore(key: U256, value: U256) {
+pub(crate) fn sys_sstore(key: U256, value: U256) {
     let key = key.as_limbs();
     let value = value.as_limbs();
 
@@ -85,6 +132,33 @@ pub fn sstore(key: U256, value: U256) {
     }
 }
 
+pub fn tload(key: U256) -> U256 {
+    let key = key.as_limbs();
+    let (val0, val1, val2, val3): (u64, u64, u64, u64);
+    unsafe {
+        asm!(
+            "ecall",
+            lateout("a0") val0, lateout("a1") val1, lateout("a2") val2, lateout("a3") val3,
+            in("a0") key[0], in("a1") key[1], in("a2") key[2], in("a3") key[3],
+            in("t0") u8::from(Syscall::TLoad));
+    }
+    U256::from_limbs([val0, val1, val2, val3])
+}
+
+pub fn tstore(key: U256, value: U256) {
+    let key = key.as_limbs();
+    let value = value.as_limbs();
+
+    unsafe {
+        asm!(
+            "ecall",
+            in("a0") key[0], in("a1") key[1], in("a2") key[2], in("a3") key[3],
+            in("a4") value[0], in("a5") value[1], in("a6") value[2], in("a7") value[3],
+            in("t0") u8::from(Syscall::TStore)
+        );
+    }
+}
+
 pub fn keccak256(offset: u64, size: u64) -> U256 {
     let (first, second, third, fourth): (u64, u64, u64, u64);
     unsafe {
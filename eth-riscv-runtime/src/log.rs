@@ -1,6 +1,7 @@
 extern crate alloc;
 use alloc::vec::Vec;
 use alloy_core::primitives::B256;
+use alloy_sol_types::{EventTopic, SolType, SolValue};
 use core::arch::asm;
 use crate::Syscall;
 
@@ -10,12 +11,34 @@ pub trait Event {
 
 pub fn emit<T: Event>(event: T) {
     let (data, topics) = event.encode_log();
-    emit_log(&data, &topics.iter().map(|t| B256::from_slice(t)).collect::<Vec<_>>());
+    assert!(topics.len() <= 4, "Too many topics");
+
+    let mut words = [B256::ZERO; 4];
+    for (word, topic) in words.iter_mut().zip(&topics) {
+        *word = B256::from(*topic);
+    }
+    emit_log(&data, &words[..topics.len()]);
+
+    // Data is allocated last, so it's freed first for `GLOBAL` to reclaim both
+    drop(data);
+    drop(topics);
 }
 
+// Topic of an indexed event field: value types are stored as-is, while dynamic types, arrays
+// and structs are stored as the keccak256 hash of their encoding (as Solidity does)
+pub fn encode_topic<T>(value: &T) -> B256
+where
+    T: SolValue,
+    T::SolType: EventTopic + SolType<RustType = T>,
+{
+    <T::SolType as EventTopic>::encode_topic(value).0
+}
+
+// Emits a log with up to 4 topics (LOG0-LOG4)
 pub fn emit_log(data: &[u8], topics: &[B256]) {
-    let mut all_topics = [0u8; 96];
-    let topics = &topics[..topics.len().min(3)];
+    assert!(topics.len() <= 4, "Too many topics");
+
+    let mut all_topics = [0u8; 128];
     for (i, topic) in topics.iter().enumerate() {
         let start = i * 32;
         all_topics[start..start + 32].copy_from_slice(topic.as_ref());
@@ -0,0 +1,38 @@
+use alloy_core::primitives::U256;
+
+use crate::{revert_with_error, tload, tstore};
+
+// Transient slot holding the lock, shared with OpenZeppelin's `ReentrancyGuardTransient`:
+// keccak256(abi.encode(uint256(keccak256("openzeppelin.storage.ReentrancyGuard")) - 1)) & ~bytes32(uint256(0xff))
+pub const REENTRANCY_GUARD_SLOT: U256 = U256::from_limbs([
+    0x486d003becc55f00,
+    0x46e071723d6817e2,
+    0x2223018b32b4d1fa,
+    0x9b779b17422d0df9,
+]);
+
+// Selector of `ReentrancyGuardReentrantCall()`
+pub const REENTRANT_CALL_SELECTOR: [u8; 4] = [0x3e, 0xe5, 0xae, 0xb5];
+
+// Returns whether a `#[nonreentrant]` method is currently executing
+pub fn entered() -> bool {
+    tload(REENTRANCY_GUARD_SLOT) != U256::ZERO
+}
+
+// Reverts if a `#[nonreentrant]` method is currently executing
+pub fn check() {
+    if entered() {
+        revert_with_error(&REENTRANT_CALL_SELECTOR);
+    }
+}
+
+// Locks the guard, reverting if it was already locked
+pub fn enter() {
+    check();
+    tstore(REENTRANCY_GUARD_SLOT, U256::from(1));
+}
+
+// Releases the guard
+pub fn exit() {
+    tstore(REENTRANCY_GUARD_SLOT, U256::ZERO);
+}
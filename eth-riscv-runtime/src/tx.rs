@@ -25,4 +25,13 @@ pub fn origin() -> Address {
     bytes[8..16].copy_from_slice(&second.to_be_bytes());
     bytes[16..20].copy_from_slice(&third.to_be_bytes()[..4]);
     Address::from_slice(&bytes)
-}
\ No newline at end of file
+}
+
+// Returns the gas left in the current call
+pub fn gas_left() -> u64 {
+    let gas: u64;
+    unsafe {
+        asm!("ecall", lateout("a0") gas, in("t0") u8::from(Syscall::Gas));
+    }
+    gas
+}
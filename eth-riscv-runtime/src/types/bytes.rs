@@ -0,0 +1,269 @@
+use super::*;
+
+extern crate alloc;
+use alloc::string::String;
+use alloy_core::primitives::Bytes;
+
+/// Implements a Solidity-like dynamic byte array, following its storage encoding:
+/// - Short values (up to 31 bytes) share the slot with their length: the data is stored in the
+///   highest-order bytes, and `len * 2` in the lowest-order byte.
+/// - Long values store `len * 2 + 1` in the slot, and the data starts at `keccak256(slot)`.
+#[derive(Default)]
+pub struct StorageBytes {
+    id: U256,
+}
+
+impl StorageLayout for StorageBytes {
+    fn allocate(slot: U256, _offset: u8) -> Self {
+        Self { id: slot }
+    }
+}
+
+impl StorageBytes {
+    pub fn len(&self) -> usize {
+        decode_len(sload(self.id))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn read(&self) -> Bytes {
+        let header = sload(self.id);
+        let len = decode_len(header);
+
+        if !is_long(header) {
+            return Bytes::copy_from_slice(&header.to_be_bytes::<32>()[..len]);
+        }
+
+        let start = data_slot(self.id);
+        let mut data = Vec::with_capacity(len);
+        for i in 0..data_slots(len) {
+            let chunk = sload(start + U256::from(i)).to_be_bytes::<32>();
+            data.extend_from_slice(&chunk[..(len - i * 32).min(32)]);
+        }
+
+        Bytes::from(data)
+    }
+
+    pub fn write(&mut self, data: &[u8]) {
+        let old_len = self.len();
+        let start = data_slot(self.id);
+
+        if data.len() < 32 {
+            let mut word = [0u8; 32];
+            word[..data.len()].copy_from_slice(data);
+            word[31] = (data.len() * 2) as u8;
+            sstore(self.id, U256::from_be_bytes(word));
+        } else {
+            sstore(self.id, U256::from(data.len() * 2 + 1));
+            for (i, chunk) in data.chunks(32).enumerate() {
+                let mut word = [0u8; 32];
+                word[..chunk.len()].copy_from_slice(chunk);
+                sstore(start + U256::from(i), U256::from_be_bytes(word));
+            }
+        }
+
+        // Like in Solidity, clear the data slots that are no longer used
+        for i in data_slots(data.len())..data_slots(old_len) {
+            sstore(start + U256::from(i), U256::ZERO);
+        }
+    }
+
+    /// Reads the byte at `index`, or `None` if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<u8> {
+        let header = sload(self.id);
+        if index >= decode_len(header) {
+            return None;
+        }
+
+        let (_, word) = self.word_of(header, index);
+        Some(word.to_be_bytes::<32>()[index % 32])
+    }
+
+    /// Overwrites the byte at `index`. Panics if it is out of bounds.
+    pub fn set(&mut self, index: usize, value: u8) {
+        let header = sload(self.id);
+        if index >= decode_len(header) {
+            panic!("Index out of bounds");
+        }
+
+        let (key, word) = self.word_of(header, index);
+        sstore(key, set_byte(word, index % 32, value));
+    }
+
+    pub fn push(&mut self, value: u8) {
+        self.extend(&[value]);
+    }
+
+    /// Appends `data`, writing only the slots from the last one in use onwards.
+    pub fn extend(&mut self, data: &[u8]) {
+        let header = sload(self.id);
+        let len = decode_len(header);
+        let new_len = len + data.len();
+
+        // Short values that stay short keep sharing the slot with their length
+        if new_len < 32 {
+            let mut word = header.to_be_bytes::<32>();
+            word[len..new_len].copy_from_slice(data);
+            word[31] = (new_len * 2) as u8;
+            sstore(self.id, U256::from_be_bytes(word));
+            return;
+        }
+
+        // Short values that become long are moved to the data slots
+        if !is_long(header) {
+            let mut value = Vec::with_capacity(new_len);
+            value.extend_from_slice(&header.to_be_bytes::<32>()[..len]);
+            value.extend_from_slice(data);
+            self.write(&value);
+            return;
+        }
+
+        // Only the last slot in use is read, as it may be partially filled
+        let start = data_slot(self.id);
+        let (mut index, mut data) = (len, data);
+        while !data.is_empty() {
+            let key = start + U256::from(index / 32);
+            let used = index % 32;
+            let mut word = if used == 0 {
+                [0u8; 32]
+            } else {
+                sload(key).to_be_bytes::<32>()
+            };
+
+            let fits = data.len().min(32 - used);
+            word[used..used + fits].copy_from_slice(&data[..fits]);
+            sstore(key, U256::from_be_bytes(word));
+            (index, data) = (index + fits, &data[fits..]);
+        }
+        sstore(self.id, U256::from(new_len * 2 + 1));
+    }
+
+    /// Removes the last byte and returns it, or `None` if the array is empty.
+    pub fn pop(&mut self) -> Option<u8> {
+        let header = sload(self.id);
+        let len = decode_len(header);
+
+        match len {
+            0 => None,
+            // Short values keep sharing the slot with their length
+            1..=31 => {
+                let value = header.to_be_bytes::<32>()[len - 1];
+                let word = set_byte(header, len - 1, 0);
+                sstore(self.id, set_byte(word, 31, ((len - 1) * 2) as u8));
+                Some(value)
+            }
+            // Long values that become short are moved back to the length slot
+            32 => {
+                let mut data = self.read().to_vec();
+                let value = data.pop();
+                self.write(&data);
+                value
+            }
+            _ => {
+                let (key, word) = self.word_of(header, len - 1);
+                let value = word.to_be_bytes::<32>()[(len - 1) % 32];
+                sstore(key, set_byte(word, (len - 1) % 32, 0));
+                sstore(self.id, U256::from((len - 1) * 2 + 1));
+                Some(value)
+            }
+        }
+    }
+
+    // Storage slot (and its current word) that holds the byte at `index`
+    fn word_of(&self, header: U256, index: usize) -> (U256, U256) {
+        if !is_long(header) {
+            return (self.id, header);
+        }
+
+        let key = data_slot(self.id) + U256::from(index / 32);
+        (key, sload(key))
+    }
+}
+
+/// Implements a Solidity-like string, stored with the same encoding as `StorageBytes`.
+#[derive(Default)]
+pub struct StorageString {
+    bytes: StorageBytes,
+}
+
+impl StorageLayout for StorageString {
+    fn allocate(slot: U256, offset: u8) -> Self {
+        Self { bytes: StorageBytes::allocate(slot, offset) }
+    }
+}
+
+impl StorageString {
+    /// Length of the string in bytes.
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub fn read(&self) -> String {
+        String::from_utf8(self.bytes.read().to_vec()).unwrap_or_else(|_| revert())
+    }
+
+    pub fn write(&mut self, value: &str) {
+        self.bytes.write(value.as_bytes())
+    }
+
+    pub fn push(&mut self, ch: char) {
+        let mut buf = [0u8; 4];
+        self.push_str(ch.encode_utf8(&mut buf));
+    }
+
+    pub fn push_str(&mut self, value: &str) {
+        self.bytes.extend(value.as_bytes());
+    }
+
+    /// Removes the last char and returns it, or `None` if the string is empty.
+    pub fn pop(&mut self) -> Option<char> {
+        // Pop the bytes of the last char, up to its first one (which isn't `0b10xxxxxx`)
+        let mut buf = [0u8; 4];
+        let mut start = buf.len();
+        while start > 0 {
+            start -= 1;
+            buf[start] = self.bytes.pop()?;
+            if buf[start] & 0xc0 != 0x80 {
+                break;
+            }
+        }
+
+        let ch = core::str::from_utf8(&buf[start..]).unwrap_or_else(|_| revert());
+        ch.chars().next()
+    }
+}
+
+// Long values are flagged by the lowest-order bit of their slot
+fn is_long(header: U256) -> bool {
+    header.bit(0)
+}
+
+fn decode_len(header: U256) -> usize {
+    if is_long(header) {
+        (header >> 1).to::<usize>()
+    } else {
+        (header.as_limbs()[0] & 0xff) as usize / 2
+    }
+}
+
+// Number of data slots used by a value of `len` bytes
+fn data_slots(len: usize) -> usize {
+    if len < 32 {
+        0
+    } else {
+        (len + 31) / 32
+    }
+}
+
+// Overwrites a single byte of a word, indexed from its highest-order byte
+fn set_byte(word: U256, index: usize, value: u8) -> U256 {
+    let mut bytes = word.to_be_bytes::<32>();
+    bytes[index] = value;
+    U256::from_be_bytes(bytes)
+}
@@ -0,0 +1,176 @@
+use super::*;
+
+use alloy_core::primitives::B256;
+
+/// Values supported by enumerable types, like OpenZeppelin's: `bytes32`, `address` and
+/// `uint256`. They are all stored as full words, so their values match those of OpenZeppelin.
+pub trait EnumerableValue: StorageWord {}
+
+impl EnumerableValue for B256 {}
+impl EnumerableValue for Address {}
+impl EnumerableValue for U256 {}
+
+/// Implements a set with O(1) add, remove and contains, whose values can be enumerated.
+///
+/// Follows the storage layout of OpenZeppelin's `EnumerableSet`, so that its data can be read
+/// by existing tools. Values are stored as full words (`bytes32`) in any case:
+/// - `_values`: dynamic array with the values, at the first slot.
+/// - `_positions`: mapping from each value to its 1-based index (`0` if absent), at the second.
+///
+/// Note that removals swap the last value into the place of the removed one, so the order of
+/// the values isn't preserved.
+pub struct EnumerableSet<T> {
+    values: StorageVec<U256>,
+    // Keys are encoded as a single word, so they are stored like OpenZeppelin's `bytes32` keys
+    positions: Mapping<U256, Slot<U256>>,
+    _pd: PhantomData<T>,
+}
+
+impl<T> StorageLayout for EnumerableSet<T> {
+    const SLOTS: usize = 2;
+
+    fn allocate(slot: U256, _offset: u8) -> Self {
+        Self {
+            values: StorageVec::allocate(slot, 0),
+            positions: Mapping::allocate(slot + U256::from(1), 0),
+            _pd: PhantomData::default(),
+        }
+    }
+}
+
+impl<T: EnumerableValue> EnumerableSet<T> {
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.positions.get(value.to_word()).read() != U256::ZERO
+    }
+
+    /// Adds a value to the set. Returns `false` if it was already present.
+    pub fn add(&mut self, value: T) -> bool {
+        let word = value.to_word();
+        if self.positions.get(word).read() != U256::ZERO {
+            return false;
+        }
+
+        self.values.push(word);
+        self.positions.get(word).write(U256::from(self.values.len()));
+        true
+    }
+
+    /// Removes a value from the set. Returns `false` if it wasn't present.
+    pub fn remove(&mut self, value: T) -> bool {
+        let word = value.to_word();
+        let position = self.positions.get(word).read();
+        if position == U256::ZERO {
+            return false;
+        }
+
+        // Move the last value to the place of the removed one, and then pop it
+        let (index, last_index) = (position.to::<usize>() - 1, self.values.len() - 1);
+        if index != last_index {
+            let last = self.values.get(last_index).unwrap_or_else(|| revert());
+            self.values.set(index, last);
+            self.positions.get(last).write(position);
+        }
+        self.values.pop();
+        self.positions.get(word).write(U256::ZERO);
+
+        true
+    }
+
+    /// Reads the value at `index`, or `None` if it is out of bounds.
+    pub fn at(&self, index: usize) -> Option<T> {
+        self.values.get(index).map(T::from_word)
+    }
+
+    /// Reads all the values. Note that this loads the whole set from storage.
+    pub fn values(&self) -> Vec<T> {
+        self.iter().collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        (0..self.len()).filter_map(move |index| self.at(index))
+    }
+}
+
+/// Implements a mapping whose keys can be enumerated, with O(1) set, remove and get.
+///
+/// Follows the storage layout of OpenZeppelin's `EnumerableMap`, so that its data can be read
+/// by existing tools. Keys and values are stored as full words (`bytes32`) in any case:
+/// - `_keys`: an `EnumerableSet` with the keys, at the first two slots.
+/// - `_values`: mapping from each key to its value, at the third slot.
+pub struct EnumerableMap<K, V> {
+    keys: EnumerableSet<K>,
+    values: Mapping<U256, Slot<U256>>,
+    _pd: PhantomData<V>,
+}
+
+impl<K, V> StorageLayout for EnumerableMap<K, V> {
+    const SLOTS: usize = 3;
+
+    fn allocate(slot: U256, _offset: u8) -> Self {
+        Self {
+            keys: EnumerableSet::allocate(slot, 0),
+            values: Mapping::allocate(slot + U256::from(2), 0),
+            _pd: PhantomData::default(),
+        }
+    }
+}
+
+impl<K: EnumerableValue, V: EnumerableValue> EnumerableMap<K, V> {
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn contains(&self, key: K) -> bool {
+        self.keys.contains(key)
+    }
+
+    /// Reads the value of a key, or `None` if it isn't present.
+    pub fn get(&self, key: K) -> Option<V> {
+        let word = key.to_word();
+        if !self.keys.contains(key) {
+            return None;
+        }
+
+        Some(V::from_word(self.values.get(word).read()))
+    }
+
+    /// Adds or updates a key-value pair. Returns `true` if the key wasn't present before.
+    pub fn set(&mut self, key: K, value: V) -> bool {
+        self.values.get(key.to_word()).write(value.to_word());
+        self.keys.add(key)
+    }
+
+    /// Removes a key-value pair. Returns `false` if the key wasn't present.
+    pub fn remove(&mut self, key: K) -> bool {
+        self.values.get(key.to_word()).write(U256::ZERO);
+        self.keys.remove(key)
+    }
+
+    /// Reads the key-value pair at `index`, or `None` if it is out of bounds.
+    pub fn at(&self, index: usize) -> Option<(K, V)> {
+        let key = self.keys.at(index)?;
+        let value = V::from_word(self.values.get(key.to_word()).read());
+        Some((key, value))
+    }
+
+    /// Reads all the keys. Note that this loads all of them from storage.
+    pub fn keys(&self) -> Vec<K> {
+        self.keys.values()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (K, V)> + '_ {
+        (0..self.len()).filter_map(move |index| self.at(index))
+    }
+}
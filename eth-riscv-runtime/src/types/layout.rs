@@ -0,0 +1,134 @@
+use super::*;
+
+use alloy_core::primitives::{FixedBytes, Signed, Uint};
+
+// -- STORAGE WORDS ------------------------------------------------------------
+
+impl<const BITS: usize, const LIMBS: usize> StorageWord for Uint<BITS, LIMBS> {
+    const BYTES: usize = (BITS + 7) / 8;
+
+    fn to_word(&self) -> U256 {
+        U256::from_limbs_slice(self.as_limbs())
+    }
+
+    fn from_word(word: U256) -> Self {
+        Self::from_limbs_slice(&word.as_limbs()[..LIMBS])
+    }
+}
+
+// Signed integers are stored in two's complement, using only their own bytes
+impl<const BITS: usize, const LIMBS: usize> StorageWord for Signed<BITS, LIMBS> {
+    const BYTES: usize = (BITS + 7) / 8;
+
+    fn to_word(&self) -> U256 {
+        self.into_raw().to_word()
+    }
+
+    fn from_word(word: U256) -> Self {
+        Self::from_raw(Uint::from_word(word))
+    }
+}
+
+impl<const N: usize> StorageWord for FixedBytes<N> {
+    const BYTES: usize = N;
+
+    fn to_word(&self) -> U256 {
+        U256::from_be_slice(self.as_slice())
+    }
+
+    fn from_word(word: U256) -> Self {
+        Self::from_slice(&word.to_be_bytes::<32>()[32 - N..])
+    }
+}
+
+impl StorageWord for Address {
+    const BYTES: usize = 20;
+
+    fn to_word(&self) -> U256 {
+        U256::from_be_slice(self.as_slice())
+    }
+
+    fn from_word(word: U256) -> Self {
+        Self::from_slice(&word.to_be_bytes::<32>()[12..])
+    }
+}
+
+impl StorageWord for bool {
+    const BYTES: usize = 1;
+
+    fn to_word(&self) -> U256 {
+        U256::from(*self as u8)
+    }
+
+    fn from_word(word: U256) -> Self {
+        word != U256::ZERO
+    }
+}
+
+macro_rules! impl_storage_word_for_int {
+    ($($ty:ty => $unsigned:ty),*) => {$(
+        impl StorageWord for $ty {
+            const BYTES: usize = core::mem::size_of::<$ty>();
+
+            fn to_word(&self) -> U256 {
+                U256::from(*self as $unsigned)
+            }
+
+            fn from_word(word: U256) -> Self {
+                word.to::<$unsigned>() as $ty
+            }
+        }
+    )*};
+}
+
+impl_storage_word_for_int!(
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128,
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128
+);
+
+// -- COMPOSITE LAYOUTS --------------------------------------------------------
+
+/// Fixed-size arrays use consecutive slots. Elements smaller than a slot are packed together.
+impl<T: StorageLayout, const N: usize> StorageLayout for [T; N] {
+    const SLOTS: usize = if T::BYTES < 32 {
+        let per_slot = 32 / T::BYTES;
+        (N + per_slot - 1) / per_slot
+    } else {
+        N * T::SLOTS
+    };
+
+    fn allocate(slot: U256, _offset: u8) -> Self {
+        core::array::from_fn(|i| {
+            if T::BYTES < 32 {
+                let per_slot = 32 / T::BYTES;
+                let offset = (i % per_slot) * T::BYTES;
+                T::allocate(slot + U256::from(i / per_slot), offset as u8)
+            } else {
+                T::allocate(slot + U256::from(i * T::SLOTS), 0)
+            }
+        })
+    }
+}
+
+/// Tuples are laid out like structs.
+macro_rules! impl_storage_layout_for_tuple {
+    ($($ty:ident $idx:tt),+) => {
+        impl<$($ty: StorageLayout),+> StorageLayout for ($($ty,)+) {
+            const SLOTS: usize = compute_layout([$(($ty::BYTES, $ty::SLOTS)),+]).1;
+
+            fn allocate(slot: U256, _offset: u8) -> Self {
+                let (positions, _) = compute_layout([$(($ty::BYTES, $ty::SLOTS)),+]);
+                ($(
+                    $ty::allocate(slot + U256::from(positions[$idx].0), positions[$idx].1 as u8),
+                )+)
+            }
+        }
+    };
+}
+
+impl_storage_layout_for_tuple!(A 0);
+impl_storage_layout_for_tuple!(A 0, B 1);
+impl_storage_layout_for_tuple!(A 0, B 1, C 2);
+impl_storage_layout_for_tuple!(A 0, B 1, C 2, D 3);
+impl_storage_layout_for_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_storage_layout_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
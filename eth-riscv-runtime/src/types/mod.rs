@@ -3,40 +3,100 @@ use core::marker::PhantomData;
 
 use crate::*;
 
-use alloy_sol_types::{SolType, SolValue};
+use alloy_sol_types::SolValue;
 
 extern crate alloc;
 use alloc::vec::Vec;
 
+mod layout;
+pub use r55_types::compute_layout;
+
 mod mapping;
 pub use mapping::Mapping;
 
 mod slot;
 pub use slot::Slot;
 
+mod transient;
+pub use transient::{TransientMapping, TransientSlot};
+
+mod vec;
+pub use vec::StorageVec;
+
+mod bytes;
+pub use bytes::{StorageBytes, StorageString};
+
+mod enumerable;
+pub use enumerable::{EnumerableMap, EnumerableSet, EnumerableValue};
+
 ///  STORAGE TYPES:
 ///  > Must implement the following traits:
-///     - `StorageLayout`: Allows the `storage` macro to allocate a storage slot.
-///  > Must implement one of the following traits, for external consumption:
+///     - `StorageLayout`: Allows the `storage` macro to allocate a storage slot (and offset).
+///  > Unless it is a wrapper or dynamic type it must implement the following traits:
 ///     - `DirectStorage`:  Exposes read and write capabilities of values that are directly accessed.
-///     - `IndirectStorage`:  Exposes read and write capabilities of values that are gated by a guard.
-///  > Unless it is a wrapper type (like `Mapping`) it must implement the following traits:
-///     - `StorageStorable`: Allows db storage reads and writes with abi de/encoding.
-
+///     - `StorageStorable`: Allows db storage reads and writes of (packed) storage words.
+///  > Dynamic types (like `StorageVec`, `StorageString` or `EnumerableSet`) expose their own
+///    APIs instead.
+///
+///  STORAGE VALUES:
+///  > Must implement `StorageWord`, which converts them to and from (part of) a storage word.
 
-// TODO: enhance `storage` macro to handle complex types (like tuples or custom structs)
-/// A trait for storage types that require a dedicated slot in the storage layout
+/// A trait for storage types that require a dedicated place in the storage layout.
+///
+/// Types smaller than a slot are packed together with the neighbouring ones, while the others
+/// (like mappings, arrays or structs) start and end on their own slots, as in Solidity.
 pub trait StorageLayout {
-    fn allocate(limb0: u64, limb1: u64, limb2: u64, limb3: u64) -> Self;
+    /// Number of bytes used within a slot (`32` for types that use whole slots)
+    const BYTES: usize = 32;
+    /// Number of consecutive slots used
+    const SLOTS: usize = 1;
+
+    fn allocate(slot: U256, offset: u8) -> Self;
+}
+
+/// Slot where the data of a dynamic type (like `StorageVec` or `StorageBytes`) starts.
+fn data_slot(id: U256) -> U256 {
+    let id_bytes: [u8; 32] = id.to_be_bytes();
+    keccak256(id_bytes.as_ptr() as u64, id_bytes.len() as u64)
+}
+
+/// Values that fit in a single storage word.
+/// Values smaller than a word are stored in its lowest-order bytes, so that they can be packed.
+pub trait StorageWord: Sized {
+    /// Number of bytes used within the word
+    const BYTES: usize;
+
+    fn to_word(&self) -> U256;
+    fn from_word(word: U256) -> Self;
 }
 
 /// Internal trait, for low-level storage operations.
 pub trait StorageStorable {
-    type Value: SolValue
-        + core::convert::From<<<Self::Value as SolValue>::SolType as SolType>::RustType>;
+    type Value: StorageWord;
+
+    fn __read(key: U256, offset: u8) -> Self::Value;
+    fn __write(key: U256, offset: u8, value: Self::Value);
+}
 
-    fn __read(key: U256) -> Self::Value;
-    fn __write(key: U256, value: Self::Value);
+// Reads a value from the bytes at `offset` of a storage word
+fn unpack<V: StorageWord>(word: U256, offset: u8) -> V {
+    if V::BYTES == 32 {
+        return V::from_word(word);
+    }
+
+    let mask = (U256::from(1) << (V::BYTES * 8)) - U256::from(1);
+    V::from_word((word >> (offset as usize * 8)) & mask)
+}
+
+// Writes a value to the bytes at `offset` of a storage word, keeping the rest of the word
+fn pack<V: StorageWord>(word: U256, offset: u8, value: &V) -> U256 {
+    if V::BYTES == 32 {
+        return value.to_word();
+    }
+
+    let shift = offset as usize * 8;
+    let mask = ((U256::from(1) << (V::BYTES * 8)) - U256::from(1)) << shift;
+    (word & !mask) | (value.to_word() << shift)
 }
 
 /// Public interface for interacting with direct storage types (like `Slot`)
@@ -47,13 +107,3 @@ where
     fn read(&self) -> V;
     fn write(&mut self, value: V);
 }
-
-/// Public interface for interacting with indirect storage types (like `MappingGuard`)
-pub trait IndirectStorage<V>
-where
-    V: StorageStorable,
-    V::Value: SolValue + core::convert::From<<<V::Value as SolValue>::SolType as SolType>::RustType>,
-{
-    fn read(&self) -> V::Value;
-    fn write(&mut self, value: V::Value);
-}
@@ -3,37 +3,37 @@ use super::*;
 use core::ops::{Add, AddAssign, Sub, SubAssign};
 
 /// Wrapper around `alloy::primitives` that can be written in a single slot (single EVM word).
+/// Values smaller than a word are packed with the neighbouring ones.
 #[derive(Default)]
 pub struct Slot<V> {
     id: U256,
+    offset: u8,
     _pd: PhantomData<V>,
 }
 
-impl<V> StorageLayout for Slot<V> {
-    fn allocate(first: u64, second: u64, third: u64, fourth: u64) -> Self {
+impl<V: StorageWord> StorageLayout for Slot<V> {
+    const BYTES: usize = V::BYTES;
+
+    fn allocate(slot: U256, offset: u8) -> Self {
         Self {
-            id: U256::from_limbs([first, second, third, fourth]),
+            id: slot,
+            offset,
             _pd: PhantomData::default(),
         }
     }
 }
 
-impl<V> StorageStorable for Slot<V>
-where
-    V: SolValue + core::convert::From<<<V as SolValue>::SolType as SolType>::RustType>,
-{
+impl<V: StorageWord> StorageStorable for Slot<V> {
     type Value = V;
 
-    fn __read(key: U256) -> Self::Value {
-        let bytes: [u8; 32] = sload(key).to_be_bytes();
-        V::abi_decode(&bytes, false).unwrap_or_else(|_| revert())
+    fn __read(key: U256, offset: u8) -> Self::Value {
+        unpack(sload(key), offset)
     }
 
-    fn __write(key: U256, value: Self::Value) {
-        let bytes = value.abi_encode();
-        let mut padded = [0u8; 32];
-        padded[..bytes.len()].copy_from_slice(&bytes);
-        sstore(key, U256::from_be_bytes(padded));
+    fn __write(key: U256, offset: u8, value: Self::Value) {
+        // Packed values must preserve the rest of the word
+        let word = if V::BYTES < 32 { sload(key) } else { U256::ZERO };
+        sstore(key, pack(word, offset, &value));
     }
 }
 
@@ -42,11 +42,11 @@ where
     Self: StorageStorable<Value = V>,
 {
     fn read(&self) -> V {
-        Self::__read(self.id)
+        Self::__read(self.id, self.offset)
     }
 
     fn write(&mut self, value: V) {
-        Self::__write(self.id, value)
+        Self::__write(self.id, self.offset, value)
     }
 }
 
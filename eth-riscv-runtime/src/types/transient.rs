@@ -0,0 +1,47 @@
+use super::*;
+
+/// Wrapper around `alloy::primitives` that can be written in a single transient slot (EIP-1153).
+/// Its value is discarded at the end of the transaction.
+#[derive(Default)]
+pub struct TransientSlot<V> {
+    id: U256,
+    _pd: PhantomData<V>,
+}
+
+/// Implements a Solidity-like Mapping type, whose values live in transient storage.
+pub type TransientMapping<K, V> = Mapping<K, TransientSlot<V>>;
+
+// Transient values aren't packed, so each of them uses a whole slot
+impl<V: StorageWord> StorageLayout for TransientSlot<V> {
+    fn allocate(slot: U256, _offset: u8) -> Self {
+        Self {
+            id: slot,
+            _pd: PhantomData::default(),
+        }
+    }
+}
+
+impl<V: StorageWord> StorageStorable for TransientSlot<V> {
+    type Value = V;
+
+    fn __read(key: U256, offset: u8) -> Self::Value {
+        unpack(tload(key), offset)
+    }
+
+    fn __write(key: U256, offset: u8, value: Self::Value) {
+        tstore(key, pack(U256::ZERO, offset, &value));
+    }
+}
+
+impl<V> DirectStorage<V> for TransientSlot<V>
+where
+    Self: StorageStorable<Value = V>,
+{
+    fn read(&self) -> V {
+        Self::__read(self.id, 0)
+    }
+
+    fn write(&mut self, value: V) {
+        Self::__write(self.id, 0, value)
+    }
+}
@@ -0,0 +1,84 @@
+use super::*;
+
+/// Implements a Solidity-like dynamic array.
+/// Its length is stored in its own slot, while the elements start at `keccak256(slot)`.
+/// Elements smaller than a slot are packed together.
+///
+/// Elements are values that fit in a slot (`StorageWord`), accessed by value like a `Slot`.
+/// Vecs of storage types (structs, arrays, or other dynamic types) aren't supported.
+#[derive(Default)]
+pub struct StorageVec<V> {
+    id: U256,
+    _pd: PhantomData<V>,
+}
+
+impl<V> StorageLayout for StorageVec<V> {
+    fn allocate(slot: U256, _offset: u8) -> Self {
+        Self {
+            id: slot,
+            _pd: PhantomData::default(),
+        }
+    }
+}
+
+impl<V: StorageWord> StorageVec<V> {
+    pub fn len(&self) -> usize {
+        sload(self.id).to::<usize>()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Reads the element at `index`, or `None` if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<V> {
+        if index >= self.len() {
+            return None;
+        }
+
+        Some(self.element(index).read())
+    }
+
+    /// Overwrites the element at `index`. Panics if it is out of bounds.
+    pub fn set(&mut self, index: usize, value: V) {
+        if index >= self.len() {
+            panic!("Index out of bounds");
+        }
+
+        self.element(index).write(value);
+    }
+
+    pub fn push(&mut self, value: V) {
+        let len = self.len();
+        self.element(len).write(value);
+        sstore(self.id, U256::from(len + 1));
+    }
+
+    /// Removes the last element and returns it, or `None` if the vec is empty.
+    /// Like in Solidity, the storage of the removed element is cleared.
+    pub fn pop(&mut self) -> Option<V> {
+        let len = self.len();
+        if len == 0 {
+            return None;
+        }
+
+        let mut element = self.element(len - 1);
+        let value = element.read();
+        element.write(V::from_word(U256::ZERO));
+        sstore(self.id, U256::from(len - 1));
+
+        Some(value)
+    }
+
+    // Storage location of the element at `index`
+    fn element(&self, index: usize) -> Slot<V> {
+        let (slot, offset) = if V::BYTES < 32 {
+            let per_slot = 32 / V::BYTES;
+            (index / per_slot, (index % per_slot) * V::BYTES)
+        } else {
+            (index, 0)
+        };
+
+        Slot::allocate(data_slot(self.id) + U256::from(slot), offset as u8)
+    }
+}
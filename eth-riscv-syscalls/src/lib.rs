@@ -55,46 +55,84 @@ macro_rules! syscalls {
 // as described on https://www.evm.codes.
 //
 // t0: 0x20, opcode for keccak256, a0: offset, a1: size, returns keccak256 hash
+// t0: 0x30, opcode for address, returns an address
+// t0: 0x31, opcode for balance, a0-a2: address, returns 256-bit value
 // t0: 0x32, opcode for origin, returns an address
 // t0: 0x33, opcode for caller, returns an address
 // t0: 0x34, opcode for callvalue, a0: first limb, a1: second limb, a2: third limb, a3: fourth limb, returns 256-bit value
+// t0: 0x38, opcode for codesize, returns 64-bit value
 // t0: 0x3A, opcode for gasprice, returns 256-bit value
+// t0: 0x3b, opcode for extcodesize, a0-a2: address, returns 64-bit value
+// t0: 0x3c, opcode for extcodecopy, a0-a2: address, a3: memory offset, a4: code offset, a5: size, returns nothing
 // t0: 0x3d, opcode for returndatasize, returns 64-bit value
 // t0: 0x3e, opcode for returndatacopy, a0: memory offset, a1: return data offset, a2: return data size, returns nothing
+// t0: 0x3f, opcode for extcodehash, a0-a2: address, returns 256-bit value
+// t0: 0x40, opcode for blockhash, a0: block number, returns 256-bit value
+// t0: 0x41, opcode for coinbase, returns an address
+// t0: 0x44, opcode for prevrandao, returns 256-bit value
+// t0: 0x47, opcode for selfbalance, returns 256-bit value
+// t0: 0x4a, opcode for blobbasefee, returns 256-bit value
+// t0: 0x5a, opcode for gas, returns 64-bit value
 // t0: 0x54, opcode for sload, a0: storage key, returns 256-bit value
 // t0: 0x55, opcode for sstore, a0-a3: 256-bit storage key, a4-a7: 256-bit storage value, returns nothing
-// t0: 0xf0, opcode for create, args: a0: 64-bit value, a1: calldata offset, a2: calldata size, returns an address
-// t0: 0xf1, opcode for call, args: a0-a2: address, a3: 64-bit value, a4: calldata offset, a5: calldata size
-// t0: 0xfa, opcode for staticcall, args: a0-a2: address, a3: 64-bit value, a4: calldata offset, a5: calldata size
+// t0: 0x5c, opcode for tload, a0-a3: 256-bit storage key, returns 256-bit value
+// t0: 0x5d, opcode for tstore, a0-a3: 256-bit storage key, a4-a7: 256-bit storage value, returns nothing
+// t0: 0xf0, opcode for create, args: a0: value offset, a1: calldata offset, a2: calldata size, a3: gas limit, returns an address
+// t0: 0xf1, opcode for call, args: a0-a2: address, a3: value offset, a4: calldata offset, a5: calldata size, a6: gas limit, returns 1 on success and 0 on failure
+// t0: 0xf5, opcode for create2, args: a0: value offset, a1: calldata offset, a2: calldata size, a3: salt offset, a4: gas limit, returns an address
+// t0: 0xf4, opcode for delegatecall, args: a0-a2: address, a3: calldata offset, a4: calldata size, a5: gas limit, returns 1 on success and 0 on failure
+// t0: 0xfa, opcode for staticcall, args: a0-a2: address, a3: value offset, a4: calldata offset, a5: calldata size, a6: gas limit, returns 1 on success and 0 on failure
+// note: values are passed as a pointer to a 256-bit big-endian word, or 0 when no value is sent
+// note: gas limits are capped to all but 1/64th of the caller's remaining gas (EIP-150)
 // t0: 0xf3, opcode for return, a0: memory address of data, a1: length of data in bytes, doesn't return
 // t0: 0xfd, opcode for revert, doesn't return
+// t0: 0xff, opcode for selfdestruct, a0-a2: beneficiary address, doesn't return
+// t0: 0xa0, opcode for log, a0: data offset, a1: data size, a2: topics offset, a3: number of topics (up to 4), returns nothing
 //
 // The following syscalls are R55 exceptions which do not correspond to any EVM opcode.
 // Because of that, they use (unused) EVM opcodes which RISC-V already implements.
 //
-// t0: 0x01, used to retrieve the created address cached in `RVEmu`
+// t0: 0x01, used to retrieve the address created by the last create, a0: memory offset, returns 1 on success
+//       and 0 if the deployment failed (its revert data is then available as return data)
 
 syscalls!(
     // EVM opcodes
     (0x20, Keccak256, "keccak256"),
+    (0x30, Address, "address"),
+    (0x31, Balance, "balance"),
     (0x32, Origin, "origin"),
     (0x33, Caller, "caller"),
     (0x34, CallValue, "callvalue"),
+    (0x38, CodeSize, "codesize"),
     (0x3A, GasPrice, "gasprice"),
+    (0x3B, ExtCodeSize, "extcodesize"),
+    (0x3C, ExtCodeCopy, "extcodecopy"),
     (0x3D, ReturnDataSize, "returndatasize"),
     (0x3E, ReturnDataCopy, "returndatacopy"),
+    (0x3F, ExtCodeHash, "extcodehash"),
+    (0x40, BlockHash, "blockhash"),
+    (0x41, Coinbase, "coinbase"),
     (0x42, Timestamp, "timestamp"),
     (0x43, Number, "number"),
+    (0x44, PrevRandao, "prevrandao"),
     (0x45, GasLimit, "gaslimit"),
     (0x46, ChainId, "chainid"),
+    (0x47, SelfBalance, "selfbalance"),
     (0x48, BaseFee, "basefee"),
+    (0x4A, BlobBaseFee, "blobbasefee"),
     (0x54, SLoad, "sload"),
     (0x55, SStore, "sstore"),
+    (0x5A, Gas, "gas"),
+    (0x5C, TLoad, "tload"),
+    (0x5D, TStore, "tstore"),
     (0xf0, Create, "create"),
     (0xf1, Call, "call"),
+    (0xf4, DelegateCall, "delegatecall"),
+    (0xf5, Create2, "create2"),
     (0xfa, StaticCall, "staticcall"),
     (0xf3, Return, "return"),
     (0xfd, Revert, "revert"),
+    (0xff, SelfDestruct, "selfdestruct"),
     (0xA0, Log, "log"),
     // R55 exceptions
     (0x01, ReturnCreateAddress, "returncreateaddress"),
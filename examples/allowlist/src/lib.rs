@@ -0,0 +1,72 @@
+#![no_std]
+#![no_main]
+
+use core::default::Default;
+
+use alloy_core::primitives::{Address, U256};
+use contract_derive::{contract, storage};
+use eth_riscv_runtime::types::*;
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+// Members and quotas can be enumerated, and use the storage layout of OpenZeppelin's types
+#[storage]
+pub struct Allowlist {
+    admin: Slot<Address>,
+    members: EnumerableSet<Address>,
+    quotas: EnumerableMap<Address, U256>,
+}
+
+#[contract]
+impl Allowlist {
+    // -- CONSTRUCTOR ----------------------------------------------------------
+    pub fn new(admin: Address) -> Self {
+        let mut allowlist = Allowlist::default();
+        allowlist.admin.write(admin);
+        allowlist
+    }
+
+    // -- STATE MODIFYING FUNCTIONS --------------------------------------------
+    pub fn add(&mut self, member: Address) -> bool {
+        if msg_sender() != self.admin.read() { panic!("Only admin") };
+        self.members.add(member)
+    }
+
+    pub fn remove(&mut self, member: Address) -> bool {
+        if msg_sender() != self.admin.read() { panic!("Only admin") };
+        self.quotas.remove(member);
+        self.members.remove(member)
+    }
+
+    pub fn set_quota(&mut self, member: Address, quota: U256) -> bool {
+        if msg_sender() != self.admin.read() { panic!("Only admin") };
+        if !self.members.contains(member) { panic!("Not a member") };
+        self.quotas.set(member, quota)
+    }
+
+    // -- READ-ONLY FUNCTIONS --------------------------------------------------
+    pub fn is_member(&self, member: Address) -> bool {
+        self.members.contains(member)
+    }
+
+    pub fn member_count(&self) -> U256 {
+        U256::from(self.members.len())
+    }
+
+    pub fn member_at(&self, index: U256) -> Option<Address> {
+        self.members.at(index.saturating_to::<usize>())
+    }
+
+    pub fn members(&self) -> Vec<Address> {
+        self.members.values()
+    }
+
+    pub fn quota_of(&self, member: Address) -> Option<U256> {
+        self.quotas.get(member)
+    }
+
+    pub fn quota_at(&self, index: U256) -> Option<(Address, U256)> {
+        self.quotas.at(index.saturating_to::<usize>())
+    }
+}
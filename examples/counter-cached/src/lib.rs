@@ -0,0 +1 @@
+../../counter/src/lib.rs
\ No newline at end of file
@@ -0,0 +1,53 @@
+#![no_std]
+#![no_main]
+
+use core::default::Default;
+
+use alloy_core::primitives::{Address, Bytes, U256};
+use contract_derive::{contract, storage};
+use eth_riscv_runtime::types::*;
+
+extern crate alloc;
+
+// Also built as `counter-cached`, which enables the storage cache of the runtime, so that both
+// storage paths can be compared on the same contract
+#[storage]
+pub struct Counter {
+    count: Slot<U256>,
+}
+
+#[contract]
+impl Counter {
+    pub fn new() -> Self {
+        Counter::default()
+    }
+
+    // Increments the count one at a time, reading and writing its slot on every step
+    pub fn increment(&mut self, times: U256) {
+        for _ in 0..times.to::<u64>() {
+            self.count += U256::from(1);
+        }
+    }
+
+    pub fn increment_and_revert(&mut self, times: U256) {
+        self.increment(times);
+        panic!("Reverted");
+    }
+
+    pub fn increment_and_destroy(&mut self, times: U256, beneficiary: Address) {
+        self.increment(times);
+        selfdestruct(beneficiary);
+    }
+
+    // Increments the count before and after calling back into itself, returning the final count
+    pub fn increment_around(&mut self, data: Bytes) -> U256 {
+        self.count += U256::from(1);
+        call_contract(account::this(), U256::ZERO, &data, Some(0), None).expect("Call failed");
+        self.count += U256::from(1);
+        self.count.read()
+    }
+
+    pub fn count(&self) -> U256 {
+        self.count.read()
+    }
+}
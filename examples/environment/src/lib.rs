@@ -0,0 +1,65 @@
+#![no_std]
+#![no_main]
+
+use core::default::Default;
+
+use alloy_core::primitives::{Address, B256, U256};
+use contract_derive::contract;
+use eth_riscv_runtime::{account, block, tx};
+
+extern crate alloc;
+
+#[derive(Default)]
+pub struct Environment;
+
+#[contract]
+impl Environment {
+    pub fn this(&self) -> Address {
+        account::this()
+    }
+
+    pub fn balance(&self, addr: Address) -> U256 {
+        account::balance(addr)
+    }
+
+    pub fn self_balance(&self) -> U256 {
+        account::self_balance()
+    }
+
+    pub fn is_contract(&self, addr: Address) -> bool {
+        account::is_contract(addr)
+    }
+
+    pub fn self_code_size(&self) -> U256 {
+        U256::from(account::self_code_size())
+    }
+
+    pub fn code_hash(&self, addr: Address) -> B256 {
+        account::code_hash(addr)
+    }
+
+    // Returns the first 32 bytes of an account's code
+    pub fn code_head(&self, addr: Address) -> B256 {
+        B256::from_slice(&account::code_copy(addr, 0, 32))
+    }
+
+    pub fn coinbase(&self) -> Address {
+        block::coinbase()
+    }
+
+    pub fn prev_randao(&self) -> B256 {
+        block::prev_randao()
+    }
+
+    pub fn blob_base_fee(&self) -> U256 {
+        block::blob_base_fee()
+    }
+
+    pub fn block_hash(&self, number: U256) -> B256 {
+        block::block_hash(number.to())
+    }
+
+    pub fn gas_left(&self) -> U256 {
+        U256::from(tx::gas_left())
+    }
+}
@@ -0,0 +1,24 @@
+#![no_std]
+#![no_main]
+
+use core::default::Default;
+
+use alloy_core::primitives::Address;
+use contract_derive::contract;
+
+extern crate alloc;
+
+// Destroys itself within its own deployment, sending its balance to the beneficiary
+#[derive(Default)]
+pub struct Ephemeral;
+
+#[contract]
+impl Ephemeral {
+    pub fn new(beneficiary: Address) -> Self {
+        eth_riscv_runtime::selfdestruct(beneficiary)
+    }
+
+    pub fn alive(&self) -> bool {
+        true
+    }
+}
@@ -6,9 +6,10 @@ use core::default::Default;
 use contract_derive::{contract, payable, storage, Event, Error};
 use eth_riscv_runtime::types::*;
 
-use alloy_core::primitives::{Address, U256};
+use alloy_core::primitives::{Address, U256, U8};
 
 extern crate alloc;
+use alloc::{string::String, vec::Vec};
 
 // -- EVENTS -------------------------------------------------------------------
 #[derive(Event)]
@@ -56,16 +57,20 @@ pub struct ERC20 {
     balance_of: Mapping<Address, Slot<U256>>,
     allowance_of: Mapping<Address, Mapping<Address, Slot<U256>>>,
     owner: Slot<Address>,
-    // TODO: handle string storage
-    // name: String, 
-    // symbol: String,
-    // decimals: u8,
+    name: StorageString,
+    symbol: StorageString,
+    decimals: Slot<U8>,
 }
 
 #[contract]
 impl ERC20 {
     // -- CONSTRUCTOR ----------------------------------------------------------
     pub fn new(owner: Address) -> Self {
+        if owner == Address::ZERO {
+            let err = eth_riscv_runtime::Error::abi_encode(&ERC20Error::ZeroAddress);
+            eth_riscv_runtime::revert_with_error(&err);
+        }
+
         // Init the contract
         let mut erc20 = ERC20::default();
 
@@ -85,7 +90,7 @@ impl ERC20 {
         if to == Address::ZERO { return Err(ERC20Error::ZeroAddress) };
 
         // Increase user balance
-        let to_balance = self.balance_of[to].read();
+        let to_balance = self.balance_of.get(to).read();
         self.balance_of[to].write(to_balance + amount);
 
         // Increase total supply
@@ -96,6 +101,24 @@ impl ERC20 {
         Ok(true)
     }
 
+    pub fn airdrop(&mut self, recipients: Vec<Address>, amount: U256) -> Result<bool, ERC20Error> {
+        // Perform sanity checks
+        if msg_sender() != self.owner.read() { return Err(ERC20Error::OnlyOwner) };
+        if amount == U256::ZERO { return Err(ERC20Error::ZeroAmount) };
+
+        // Increase user balances
+        for &to in &recipients {
+            if to == Address::ZERO { return Err(ERC20Error::ZeroAddress) };
+
+            self.balance_of[to] += amount;
+            log::emit(Transfer::new(Address::ZERO, to, amount));
+        }
+
+        // Increase total supply
+        self.total_supply += amount * U256::from(recipients.len());
+        Ok(true)
+    }
+
     pub fn approve(&mut self, spender: Address, amount: U256) -> Result<bool, ERC20Error> {
         let owner = msg_sender();
 
@@ -120,8 +143,8 @@ impl ERC20 {
         if from == to { return Err(ERC20Error::SelfTransfer) };
 
         // Read user balances
-        let from_balance = self.balance_of[from].read();
-        let to_balance = self.balance_of[to].read();
+        let from_balance = self.balance_of.get(from).read();
+        let to_balance = self.balance_of.get(to).read();
 
         // Ensure enough balance
         if from_balance < amount { return Err(ERC20Error::InsufficientBalance(from_balance)) }
@@ -144,18 +167,18 @@ impl ERC20 {
         if from == to { return Err(ERC20Error::SelfTransfer) };
 
         // Ensure enough allowance
-        let allowance = self.allowance_of[from][msg_sender].read();
+        let allowance = self.allowance_of.get(from).get(msg_sender).read();
         if allowance < amount { return Err(ERC20Error::InsufficientAllowance(allowance)) };
 
         // Ensure enough balance
-        let from_balance = self.balance_of[from].read();
+        let from_balance = self.balance_of.get(from).read();
         if from_balance < amount { return Err(ERC20Error::InsufficientBalance(from_balance)) };
 
         // Update state
         self.allowance_of[from][msg_sender].write(allowance - amount);
         self.balance_of[from].write(from_balance - amount);
         
-        let to_balance = self.balance_of[to].read();
+        let to_balance = self.balance_of.get(to).read();
         self.balance_of[to].write(to_balance + amount);
 
         // Emit event + return 
@@ -178,20 +201,44 @@ impl ERC20 {
         Ok(true)
     }
 
+    pub fn set_metadata(&mut self, name: String, symbol: String, decimals: U8) -> Result<bool, ERC20Error> {
+        // Perform safety check
+        if msg_sender() != self.owner.read() { return Err(ERC20Error::OnlyOwner) };
+
+        // Update state
+        self.name.write(&name);
+        self.symbol.write(&symbol);
+        self.decimals.write(decimals);
+
+        Ok(true)
+    }
+
     // -- READ-ONLY FUNCTIONS --------------------------------------------------
     pub fn owner(&self) -> Address {
         self.owner.read()
     }
 
+    pub fn name(&self) -> String {
+        self.name.read()
+    }
+
+    pub fn symbol(&self) -> String {
+        self.symbol.read()
+    }
+
+    pub fn decimals(&self) -> U8 {
+        self.decimals.read()
+    }
+
     pub fn total_supply(&self) -> U256 {
         self.total_supply.read()
     }
 
     pub fn balance_of(&self, owner: Address) -> U256 {
-        self.balance_of[owner].read()
+        self.balance_of.get(owner).read()
     }
 
     pub fn allowance(&self, owner: Address, spender: Address) -> U256 {
-        self.allowance_of[owner][spender].read()
+        self.allowance_of.get(owner).get(spender).read()
     }
 }
@@ -3,27 +3,84 @@
 
 use core::default::Default;
 
-use alloy_core::primitives::{Address, U256, Bytes};
-use contract_derive::{contract, show_streams};
+use alloy_core::primitives::{Address, B256, U64, U256, Bytes};
+use contract_derive::{contract, nonreentrant, show_streams, Error, Event, SolStruct};
+use eth_riscv_runtime::CallError;
 
 extern crate alloc;
+use alloc::{string::String, vec::Vec};
 
 use erc20::{ERC20Error, IERC20};
 
 mod deployable;
 use deployable::ERC20;
 
+#[derive(SolStruct, Debug)]
+pub struct Limit {
+    pub token: Address,
+    pub max: U256,
+}
+
+#[derive(Event)]
+pub struct LimitChecked {
+    #[indexed]
+    pub caller: Address,
+    pub limit: Limit,
+    pub amount: U256,
+}
+
+#[derive(Event)]
+#[anonymous]
+pub struct Checkpoint {
+    #[indexed]
+    pub caller: Address,
+    #[indexed]
+    pub token: Address,
+    #[indexed]
+    pub max: U256,
+    #[indexed]
+    pub amount: U256,
+}
+
+#[derive(Event)]
+pub struct Note {
+    #[indexed]
+    pub caller: Address,
+    #[indexed]
+    pub memo: String,
+    #[indexed]
+    pub limit: Limit,
+}
+
+#[derive(Event)]
+pub struct LimitsChecked {
+    #[indexed]
+    pub caller: Address,
+    pub limits: Vec<Limit>,
+}
+
+#[derive(Error)]
+pub enum ERC20xError {
+    ExceedsLimit { limit: Limit, amount: U256 },
+}
+
 #[derive(Default, )]
 pub struct ERC20x;
 
 #[contract]
 impl ERC20x {
     // Deploys a new ERC20 token instance
-    pub fn x_deploy(&mut self, owner: Address) -> (Address, Address) {
-        let token = ERC20::deploy(owner).with_ctx(self);            // IERC20<ReadWrite>
+    pub fn x_deploy(&mut self, owner: Address) -> Result<(Address, Address), CallError<ERC20Error>> {
+        let token = ERC20::deploy(owner).with_ctx::<_, _, ERC20Error>(self)?; // IERC20<ReadWrite>
         let owner = token.owner().expect("Unable to get owner");
 
-        (token.address(), owner)
+        Ok((token.address(), owner))
+    }
+
+    // Deploys a new ERC20 token instance at a deterministic address
+    pub fn x_deploy_with_salt(&mut self, owner: Address, salt: B256) -> Result<Address, CallError<ERC20Error>> {
+        let token = ERC20::deploy(owner).with_salt(salt).with_ctx::<_, _, ERC20Error>(self)?;
+        Ok(token.address())
     }
 
     // Performs a staticcall to an ERC20
@@ -32,8 +89,14 @@ impl ERC20x {
         token.balance_of(owner)
     }
 
+    // Performs a delegatecall to an ERC20, so that it reads the ERC20x storage
+    pub fn x_delegate_balance_of(&mut self, owner: Address, token_addr: Address) -> Option<U256> {
+        let token = IERC20::new(token_addr).with_delegate_ctx(self); // IERC20<Delegated>
+        token.balance_of(owner)
+    }
+
     // Performs a (mutable) call to an ERC20
-    pub fn x_mint(&mut self, to: Address, amount: U256, token_addr: Address) -> Result<bool, ERC20Error> {
+    pub fn x_mint(&mut self, to: Address, amount: U256, token_addr: Address) -> Result<bool, CallError<ERC20Error>> {
         let mut token = IERC20::new(token_addr).with_ctx(self);     // IERC20<ReadWrite>
         token.mint(to, amount)
     }
@@ -50,21 +113,78 @@ impl ERC20x {
         from: Address,
         amount: U256,
         token_addr: Address
-    ) -> Result<bool, ERC20Error> {
+    ) -> Result<bool, CallError<ERC20Error>> {
         let mut token = IERC20::new(token_addr).with_ctx(self);     // IERC20<ReadWrite>
         let to = msg_sender();
 
         // easily leverage rust's `Result<T, E>` enum to deal with call reverts
         match token.transfer_from(from, to, amount) {
-            Err(ERC20Error::InsufficientBalance(max)) => token.transfer_from(from, to, max),
-            Err(ERC20Error::InsufficientAllowance(max)) => token.transfer_from(from, to, max),
+            Err(CallError::Revert(ERC20Error::InsufficientBalance(max))) => token.transfer_from(from, to, max),
+            Err(CallError::Revert(ERC20Error::InsufficientAllowance(max))) => token.transfer_from(from, to, max),
             other => other
         }
     }
 
+    // Forwards some of the contract's ETH to the recipient
+    pub fn x_send(&mut self, to: Address, amount: U256) {
+        call_contract(to, amount, &[], Some(0), None).expect("Unable to send ETH");
+    }
+
+    // Calls a target with a capped gas limit, returning whether the call succeeded
+    pub fn x_call_with_gas(&mut self, target: Address, data: Bytes, gas_limit: U64) -> bool {
+        call_contract(target, U256::ZERO, &data, Some(0), Some(gas_limit.to())).is_ok()
+    }
+
+    // Staticcalls a target with a capped gas limit, returning whether the call succeeded
+    pub fn x_staticcall_with_gas(&self, target: Address, data: Bytes, gas_limit: U64) -> bool {
+        staticcall_contract(target, U256::ZERO, &data, Some(0), Some(gas_limit.to())).is_ok()
+    }
+
+    // Sends all of the contract's ETH to the beneficiary and halts
+    pub fn x_destroy(&mut self, beneficiary: Address) {
+        selfdestruct(beneficiary);
+    }
+
+    // Calls back into itself while holding the reentrancy guard, returning the revert data (if any)
+    #[nonreentrant]
+    pub fn x_reenter(&mut self, data: Bytes) -> Bytes {
+        match call_contract(account::this(), U256::ZERO, &data, None, None) {
+            Ok(_) => Bytes::new(),
+            Err(revert_data) => revert_data,
+        }
+    }
+
+    // Reverts with a multi-field error (including a struct) if the amount exceeds the max
+    pub fn x_check_limit(&mut self, token: Address, max: U256, amount: U256) -> Result<bool, ERC20xError> {
+        let limit = Limit { token, max };
+        if amount > max { return Err(ERC20xError::ExceedsLimit { limit, amount }) };
+
+        log::emit(LimitChecked::new(msg_sender(), limit, amount));
+        Ok(true)
+    }
+
+    // Emits an anonymous event, with all 4 topics used by indexed fields
+    pub fn x_checkpoint(&mut self, token: Address, max: U256, amount: U256) {
+        log::emit(Checkpoint::new(msg_sender(), token, max, amount));
+    }
+
+    // Emits an event with indexed dynamic and struct fields, which are hashed into the topics
+    pub fn x_note(&mut self, memo: String, token: Address, max: U256) {
+        log::emit(Note::new(msg_sender(), memo, Limit { token, max }));
+    }
+
+    // Emits an event with a vec of structs, which is encoded as an array of tuples
+    pub fn x_check_limits(&mut self, tokens: Vec<Address>, max: U256) {
+        let limits = tokens.into_iter().map(|token| Limit { token, max }).collect();
+        log::emit(LimitsChecked::new(msg_sender(), limits));
+    }
+
     // Always reverts with a str msg
     pub fn panics(&self) { panic!("This function always panics"); }
 
+    // Never returns, so it always runs out of gas
+    pub fn spin(&self) { loop { core::hint::spin_loop(); } }
+
     // If the call fails, reverts with a str msg
     pub fn x_mint_panics(&mut self, to: Address, amount: U256, token_addr: Address) -> bool {
         let mut token = IERC20::new(token_addr).with_ctx(self);     // IERC20<ReadWrite>
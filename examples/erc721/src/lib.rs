@@ -69,9 +69,10 @@ pub struct ERC721 {
     approval_of: Mapping<U256, Slot<Address>>,
     is_operator: Mapping<Address, Mapping<Address, Slot<bool>>>,
     owner: Slot<Address>,
-    // TODO: handle string storage
-    // name: String, 
-    // symbol: String,
+    name: StorageString,
+    symbol: StorageString,
+    token_uri_of: Mapping<U256, StorageString>,
+    all_tokens: StorageVec<U256>,
 }
 
 #[contract]
@@ -94,16 +95,17 @@ impl ERC721 {
         // Perform sanity checks
         if to == Address::ZERO { return Err(ERC721Error::ZeroAddress) };
         if msg_sender() != self.owner.read() { return Err(ERC721Error::OnlyOwner) }; 
-        if self.owner_of[id].read() != Address::ZERO { return Err(ERC721Error::AlreadyMinted) };
+        if self.owner_of.get(id).read() != Address::ZERO { return Err(ERC721Error::AlreadyMinted) };
 
         // Update state
         self.owner_of[id].write(to);
         
-        let balance_to = self.balance_of[to].read();
+        let balance_to = self.balance_of.get(to).read();
         self.balance_of[to].write(balance_to + U256::from(1));
 
         let total_supply = self.total_supply.read();
         self.total_supply.write(total_supply + U256::from(1));
+        self.all_tokens.push(id);
         
         // Emit event + return
         log::emit(Transfer::new(Address::ZERO, to, id));
@@ -111,10 +113,10 @@ impl ERC721 {
     }
 
     pub fn approve(&mut self, spender: Address, id: U256) -> Result<bool, ERC721Error> {
-        let owner = self.owner_of[id].read();
+        let owner = self.owner_of.get(id).read();
         
         // Perform authorization check
-        if msg_sender() != owner && !self.is_operator[owner][msg_sender()].read() {
+        if msg_sender() != owner && !self.is_operator.get(owner).get(msg_sender()).read() {
             return Err(ERC721Error::Unauthorized);
         }
 
@@ -139,14 +141,14 @@ impl ERC721 {
 
     pub fn transfer_from(&mut self, from: Address, to: Address, id: U256) -> Result<bool, ERC721Error> {
         // Perform sanity checks
-        if from != self.owner_of[id].read() { return Err(ERC721Error::WrongFrom) };
+        if from != self.owner_of.get(id).read() { return Err(ERC721Error::WrongFrom) };
         if to == Address::ZERO { return Err(ERC721Error::ZeroAddress) };
 
         // Check authorization
         let sender = msg_sender();
         if sender != from 
-            && !self.is_operator[from][sender].read()
-            && sender != self.approval_of[id].read() {
+            && !self.is_operator.get(from).get(sender).read()
+            && sender != self.approval_of.get(id).read() {
             return Err(ERC721Error::Unauthorized);
         }
 
@@ -154,10 +156,10 @@ impl ERC721 {
         self.owner_of[id].write(to);
         self.approval_of[id].write(Address::ZERO);
 
-        let balance_from = self.balance_of[from].read();
+        let balance_from = self.balance_of.get(from).read();
         self.balance_of[from].write(balance_from - U256::from(1));
 
-        let balance_to = self.balance_of[to].read();
+        let balance_to = self.balance_of.get(to).read();
         self.balance_of[to].write(balance_to + U256::from(1));
 
         // Emit event + return
@@ -178,13 +180,54 @@ impl ERC721 {
         Ok(true)
     }
 
+    pub fn set_metadata(&mut self, name: String, symbol: String) -> Result<bool, ERC721Error> {
+        // Perform safety check
+        if msg_sender() != self.owner.read() { return Err(ERC721Error::OnlyOwner) };
+
+        // Update state
+        self.name.write(&name);
+        self.symbol.write(&symbol);
+
+        Ok(true)
+    }
+
+    pub fn set_token_uri(&mut self, id: U256, uri: String) -> Result<bool, ERC721Error> {
+        // Perform sanity checks
+        if msg_sender() != self.owner.read() { return Err(ERC721Error::OnlyOwner) };
+        if self.owner_of.get(id).read() == Address::ZERO { return Err(ERC721Error::NotMinted) };
+
+        // Update state
+        self.token_uri_of[id].write(&uri);
+
+        Ok(true)
+    }
+
     // -- READ-ONLY FUNCTIONS --------------------------------------------------
     pub fn owner(&self) -> Address {
         self.owner.read()
     }
 
+    pub fn name(&self) -> String {
+        self.name.read()
+    }
+
+    pub fn symbol(&self) -> String {
+        self.symbol.read()
+    }
+
+    pub fn token_uri(&self, id: U256) -> Result<String, ERC721Error> {
+        if self.owner_of.get(id).read() == Address::ZERO {
+            return Err(ERC721Error::NotMinted);
+        }
+        Ok(self.token_uri_of.get(id).read())
+    }
+
+    pub fn token_by_index(&self, index: U256) -> Result<U256, ERC721Error> {
+        self.all_tokens.get(index.saturating_to::<usize>()).ok_or(ERC721Error::NotMinted)
+    }
+
     pub fn owner_of(&self, id: U256) -> Result<Address, ERC721Error> {
-        let owner = self.owner_of[id].read();
+        let owner = self.owner_of.get(id).read();
         if owner == Address::ZERO {
             return Err(ERC721Error::NotMinted);
         }
@@ -195,15 +238,15 @@ impl ERC721 {
         if owner == Address::ZERO {
             return Err(ERC721Error::ZeroAddress);
         }
-        Ok(self.balance_of[owner].read())
+        Ok(self.balance_of.get(owner).read())
     }
 
     pub fn get_approved(&self, id: U256) -> Address {
-        self.approval_of[id].read()
+        self.approval_of.get(id).read()
     }
 
     pub fn is_approved_for_all(&self, owner: Address, operator: Address) -> bool {
-        self.is_operator[owner][operator].read()
+        self.is_operator.get(owner).get(operator).read()
     }
 
     pub fn total_supply(&self) -> U256 {
@@ -4,7 +4,7 @@
 use core::default::Default;
 
 use alloy_core::primitives::{address, Bytes, Address, U256};
-use contract_derive::{contract, interface};
+use contract_derive::{contract, interface_from_abi};
 
 extern crate alloc;
 use alloc::{string::String, vec::Vec};
@@ -12,11 +12,8 @@ use alloc::{string::String, vec::Vec};
 #[derive(Default)]
 pub struct EVMCaller;
 
-#[interface("camelCase")]
-trait ISimpleStorage {
-    fn get(&self) -> U256;
-    fn set(&mut self, value: U256);
-}
+// Generates `ISimpleStorage` from the Solidity ABI
+interface_from_abi!("abi/ISimpleStorage.json");
 
 #[contract]
 impl EVMCaller {
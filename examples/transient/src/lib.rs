@@ -0,0 +1,51 @@
+#![no_std]
+#![no_main]
+
+use core::default::Default;
+
+use alloy_core::primitives::{Address, U256};
+use contract_derive::{contract, storage};
+use eth_riscv_runtime::types::*;
+
+extern crate alloc;
+
+#[storage]
+pub struct Transient {
+    total: Slot<U256>,
+    pending: TransientSlot<U256>,
+    pending_of: TransientMapping<Address, U256>,
+}
+
+#[contract]
+impl Transient {
+    // Accumulates an amount, which is only kept until the end of the tx
+    pub fn add(&mut self, amount: U256) -> U256 {
+        let sender = msg_sender();
+        let sender_pending = self.pending_of.get(sender).read();
+        self.pending_of[sender].write(sender_pending + amount);
+
+        let pending = self.pending.read() + amount;
+        self.pending.write(pending);
+        pending
+    }
+
+    // Accumulates two amounts within the same tx, and persists their sum
+    pub fn add_twice(&mut self, first: U256, second: U256) -> U256 {
+        self.add(first);
+        let pending = self.add(second);
+        self.total += pending;
+        pending
+    }
+
+    pub fn pending(&self) -> U256 {
+        self.pending.read()
+    }
+
+    pub fn pending_of(&self, owner: Address) -> U256 {
+        self.pending_of.get(owner).read()
+    }
+
+    pub fn total(&self) -> U256 {
+        self.total.read()
+    }
+}
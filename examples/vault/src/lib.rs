@@ -0,0 +1,156 @@
+#![no_std]
+#![no_main]
+
+use core::default::Default;
+
+use alloy_core::primitives::{Address, Bytes, U128, U16, U256, U64, U8};
+use contract_derive::{contract, storage, StorageLayout};
+use eth_riscv_runtime::types::*;
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+// Spans 2 slots: `amount` fills the first one, while the rest are packed into the second one
+#[derive(StorageLayout)]
+pub struct Position {
+    amount: Slot<U256>,
+    owner: Slot<Address>,
+    locked: Slot<bool>,
+    unlock_at: Slot<U64>,
+}
+
+// `admin`, `paused` and `fee_bps` share slot 0, and `limits` are packed into slots 2 and 3.
+// The elements of `unlocks` are packed too, 4 per slot.
+#[storage]
+pub struct Vault {
+    admin: Slot<Address>,
+    paused: Slot<bool>,
+    fee_bps: Slot<U16>,
+    total: Slot<U256>,
+    limits: [Slot<U128>; 3],
+    positions: Mapping<Address, Position>,
+    depositors: StorageVec<Address>,
+    unlocks: StorageVec<U64>,
+    memo: StorageBytes,
+}
+
+#[contract]
+impl Vault {
+    // -- CONSTRUCTOR ----------------------------------------------------------
+    pub fn new(admin: Address, fee_bps: U16) -> Self {
+        let mut vault = Vault::default();
+        vault.admin.write(admin);
+        vault.fee_bps.write(fee_bps);
+        vault
+    }
+
+    // -- STATE MODIFYING FUNCTIONS --------------------------------------------
+    pub fn deposit(&mut self, amount: U256, unlock_at: U64) {
+        if self.paused.read() { panic!("Paused") };
+
+        let sender = msg_sender();
+        let position = &mut self.positions[sender];
+        if position.amount.read() == U256::ZERO { self.depositors.push(sender) };
+        position.amount += amount;
+        position.owner.write(sender);
+        position.locked.write(true);
+        position.unlock_at.write(unlock_at);
+
+        self.total += amount;
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        if msg_sender() != self.admin.read() { panic!("Only admin") };
+        self.paused.write(paused);
+    }
+
+    pub fn set_limit(&mut self, index: U256, limit: U128) {
+        if msg_sender() != self.admin.read() { panic!("Only admin") };
+        self.limits[index.to::<usize>()].write(limit);
+    }
+
+    // Moves the last depositor to the place of the removed one
+    pub fn remove_depositor(&mut self, index: U256) {
+        if msg_sender() != self.admin.read() { panic!("Only admin") };
+        let index = index.to::<usize>();
+        if index >= self.depositors.len() { panic!("Index out of bounds") };
+
+        let last = self.depositors.pop().unwrap();
+        if index < self.depositors.len() { self.depositors.set(index, last) };
+    }
+
+    pub fn schedule_unlock(&mut self, at: U64) {
+        if msg_sender() != self.admin.read() { panic!("Only admin") };
+        self.unlocks.push(at);
+    }
+
+    pub fn reschedule_unlock(&mut self, index: U256, at: U64) {
+        if msg_sender() != self.admin.read() { panic!("Only admin") };
+        self.unlocks.set(index.to::<usize>(), at);
+    }
+
+    // Cancels the last scheduled unlock, returning it
+    pub fn cancel_unlock(&mut self) -> U64 {
+        if msg_sender() != self.admin.read() { panic!("Only admin") };
+        self.unlocks.pop().unwrap_or_else(|| panic!("No unlocks"))
+    }
+
+    pub fn set_memo(&mut self, memo: Bytes) {
+        if msg_sender() != self.admin.read() { panic!("Only admin") };
+        self.memo.write(&memo);
+    }
+
+    pub fn push_memo(&mut self, byte: U8) {
+        if msg_sender() != self.admin.read() { panic!("Only admin") };
+        self.memo.push(byte.to::<u8>());
+    }
+
+    // Removes the last byte of the memo, returning it
+    pub fn pop_memo(&mut self) -> U8 {
+        if msg_sender() != self.admin.read() { panic!("Only admin") };
+        U8::from(self.memo.pop().unwrap_or_else(|| panic!("Empty memo")))
+    }
+
+    // -- READ-ONLY FUNCTIONS --------------------------------------------------
+    pub fn position(&self, owner: Address) -> (U256, Address, bool, U64) {
+        let position = self.positions.get(owner);
+        (
+            position.amount.read(),
+            position.owner.read(),
+            position.locked.read(),
+            position.unlock_at.read(),
+        )
+    }
+
+    pub fn limit(&self, index: U256) -> U128 {
+        self.limits[index.to::<usize>()].read()
+    }
+
+    pub fn admin(&self) -> Address {
+        self.admin.read()
+    }
+
+    pub fn paused(&self) -> bool {
+        self.paused.read()
+    }
+
+    pub fn fee_bps(&self) -> U16 {
+        self.fee_bps.read()
+    }
+
+    pub fn total(&self) -> U256 {
+        self.total.read()
+    }
+
+    pub fn depositors(&self) -> Vec<Address> {
+        (0..self.depositors.len()).filter_map(|i| self.depositors.get(i)).collect()
+    }
+
+    pub fn unlocks(&self) -> Vec<U64> {
+        (0..self.unlocks.len()).filter_map(|i| self.unlocks.get(i)).collect()
+    }
+
+    pub fn memo(&self) -> Bytes {
+        self.memo.read()
+    }
+}
@@ -0,0 +1,39 @@
+#![no_std]
+#![no_main]
+
+use core::default::Default;
+
+use alloy_core::primitives::U256;
+use contract_derive::{contract, fallback, receive, storage};
+use eth_riscv_runtime::types::*;
+
+extern crate alloc;
+
+#[storage]
+pub struct Wallet {
+    received: Slot<U256>,
+    fallbacks: Slot<U256>,
+}
+
+#[contract]
+impl Wallet {
+    // Accepts plain ETH transfers
+    #[receive]
+    fn on_receive(&mut self) {
+        self.received += msg_value();
+    }
+
+    // Handles calls with an unknown selector (non-payable)
+    #[fallback]
+    fn on_fallback(&mut self) {
+        self.fallbacks += U256::from(1);
+    }
+
+    pub fn received(&self) -> U256 {
+        self.received.read()
+    }
+
+    pub fn fallbacks(&self) -> U256 {
+        self.fallbacks.read()
+    }
+}
@@ -0,0 +1,659 @@
+use r55_types::{rust_type_to_sol_type, DynSolType};
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+use syn::{
+    Attribute, Field, FnArg, GenericArgument, Ident, ImplItem, ImplItemMethod, Item, ItemEnum,
+    ItemMod, ItemStruct, Meta, NestedMeta, Pat, PathArguments, ReturnType, Type, Variant,
+    Visibility,
+};
+use tracing::warn;
+
+use crate::compile::Contract;
+
+// Structs of the contract deriving a given trait, by name
+pub(crate) type Structs<'a> = HashMap<String, &'a ItemStruct>;
+
+// Generate the Solidity-compatible JSON ABI of a contract, based on its source code
+pub fn generate_abi(contract: &Contract) -> eyre::Result<Value> {
+    let files = parse_sources(&contract.path.join("src"))?;
+    Ok(Value::Array(abi_from_files(&files)))
+}
+
+// Parses the source files of a contract, starting with `lib.rs`
+pub(crate) fn parse_sources(src_dir: &Path) -> eyre::Result<Vec<syn::File>> {
+    let mut paths = vec![src_dir.join("lib.rs")];
+    source_paths(src_dir, &mut paths)?;
+
+    paths
+        .iter()
+        .map(|path| Ok(syn::parse_file(&fs::read_to_string(path)?)?))
+        .collect()
+}
+
+fn source_paths(dir: &Path, paths: &mut Vec<PathBuf>) -> eyre::Result<()> {
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| Ok(entry?.path()))
+        .collect::<eyre::Result<Vec<_>>>()?;
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            source_paths(&path, paths)?;
+        } else if path.extension().is_some_and(|ext| ext == "rs") && !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+// Structs deriving `name` from any of the source files, including the ones in nested modules
+pub(crate) fn derived_structs<'a>(files: &'a [syn::File], name: &str) -> Structs<'a> {
+    fn collect<'a>(items: &'a [Item], name: &str, structs: &mut Structs<'a>) {
+        for item in items {
+            match item {
+                Item::Struct(item_struct) if derives(&item_struct.attrs, name) => {
+                    structs.insert(item_struct.ident.to_string(), item_struct);
+                }
+                Item::Mod(ItemMod {
+                    content: Some((_, items)),
+                    ..
+                }) => collect(items, name, structs),
+                _ => {}
+            }
+        }
+    }
+
+    let mut structs = HashMap::new();
+    for file in files {
+        collect(&file.items, name, &mut structs);
+    }
+
+    structs
+}
+
+// The contract, its events and its errors are defined in the first file (`lib.rs`).
+// Entries with types that can't be resolved (like structs imported from other crates) are left
+// out of the ABI.
+fn abi_from_files(files: &[syn::File]) -> Vec<Value> {
+    let mut abi = Vec::new();
+
+    // Struct params are encoded as tuples, so their fields must be known beforehand
+    let structs = derived_structs(files, "SolStruct");
+
+    for item in files.first().map_or(&[][..], |file| &file.items) {
+        match item {
+            // Constructor, functions and ETH handlers from the `#[contract]` impl block
+            Item::Impl(item_impl) if has_attribute(&item_impl.attrs, "contract") => {
+                for impl_item in &item_impl.items {
+                    if let ImplItem::Method(method) = impl_item {
+                        push_entry(&mut abi, &method.sig.ident, method_abi(method, &structs));
+                    }
+                }
+            }
+            // Events from `#[derive(Event)]` structs
+            Item::Struct(item_struct) if derives(&item_struct.attrs, "Event") => {
+                let entry = event_abi(item_struct, &structs).map(Some);
+                push_entry(&mut abi, &item_struct.ident, entry);
+            }
+            // Errors from `#[derive(Error)]` enums
+            Item::Enum(item_enum) if derives(&item_enum.attrs, "Error") => {
+                for variant in &item_enum.variants {
+                    let entry = error_abi(item_enum, variant, &structs).map(Some);
+                    push_entry(&mut abi, &variant.ident, entry);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    abi
+}
+
+fn push_entry(abi: &mut Vec<Value>, name: &Ident, entry: eyre::Result<Option<Value>>) {
+    match entry {
+        Ok(Some(entry)) => abi.push(entry),
+        Ok(None) => {}
+        Err(e) => warn!("Leaving `{}` out of the ABI: {}", name, e),
+    }
+}
+
+fn method_abi(method: &ImplItemMethod, structs: &Structs) -> eyre::Result<Option<Value>> {
+    let is_payable = has_attribute(&method.attrs, "payable");
+
+    // Deployments don't check the value, so constructors are always payable
+    if method.sig.ident == "new" {
+        return Ok(Some(json!({
+            "type": "constructor",
+            "inputs": inputs_abi(method, structs)?,
+            "stateMutability": "payable",
+        })));
+    }
+
+    if has_attribute(&method.attrs, "receive") {
+        return Ok(Some(
+            json!({ "type": "receive", "stateMutability": "payable" }),
+        ));
+    }
+
+    if has_attribute(&method.attrs, "fallback") {
+        let state_mutability = if is_payable { "payable" } else { "nonpayable" };
+        return Ok(Some(
+            json!({ "type": "fallback", "stateMutability": state_mutability }),
+        ));
+    }
+
+    // Only public methods are exposed by the dispatcher
+    if !matches!(method.vis, Visibility::Public(_)) {
+        return Ok(None);
+    }
+
+    let state_mutability = match method.sig.inputs.first() {
+        _ if is_payable => "payable",
+        Some(FnArg::Receiver(receiver)) if receiver.mutability.is_none() => "view",
+        _ => "nonpayable",
+    };
+
+    Ok(Some(json!({
+        "type": "function",
+        "name": method.sig.ident.to_string(),
+        "inputs": inputs_abi(method, structs)?,
+        "outputs": outputs_abi(&method.sig.output, structs)?,
+        "stateMutability": state_mutability,
+    })))
+}
+
+fn inputs_abi(method: &ImplItemMethod, structs: &Structs) -> eyre::Result<Vec<Value>> {
+    method
+        .sig
+        .inputs
+        .iter()
+        .filter_map(|arg| match arg {
+            FnArg::Typed(pat_type) => Some(pat_type),
+            FnArg::Receiver(_) => None,
+        })
+        .map(|pat_type| {
+            let name = match &*pat_type.pat {
+                Pat::Ident(pat_ident) => pat_ident.ident.to_string(),
+                _ => String::new(),
+            };
+            param_abi(&name, &pat_type.ty, structs)
+        })
+        .collect()
+}
+
+fn outputs_abi(output: &ReturnType, structs: &Structs) -> eyre::Result<Vec<Value>> {
+    let ty = match output {
+        ReturnType::Default => return Ok(Vec::new()),
+        ReturnType::Type(_, ty) => unwrap_return_type(ty),
+    };
+
+    // Tuples are returned as multiple values
+    match ty {
+        Type::Tuple(tuple) => tuple
+            .elems
+            .iter()
+            .map(|ty| param_abi("", ty, structs))
+            .collect(),
+        ty => Ok(vec![param_abi("", ty, structs)?]),
+    }
+}
+
+// The dispatcher returns the inner value of `Result<T, E>` and `Option<T>`
+fn unwrap_return_type(ty: &Type) -> &Type {
+    if let Type::Path(type_path) = ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            if segment.ident == "Result" || segment.ident == "Option" {
+                if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                    if let Some(GenericArgument::Type(inner)) = args.args.first() {
+                        return inner;
+                    }
+                }
+            }
+        }
+    }
+
+    ty
+}
+
+fn event_abi(item: &ItemStruct, structs: &Structs) -> eyre::Result<Value> {
+    let inputs = item
+        .fields
+        .iter()
+        .map(|field| {
+            let mut param = param_abi(&field_name(field), &field.ty, structs)?;
+            param["indexed"] = json!(has_attribute(&field.attrs, "indexed"));
+            Ok(param)
+        })
+        .collect::<eyre::Result<Vec<_>>>()?;
+
+    Ok(json!({
+        "type": "event",
+        "name": item.ident.to_string(),
+        "inputs": inputs,
+        "anonymous": has_attribute(&item.attrs, "anonymous"),
+    }))
+}
+
+fn error_abi(item: &ItemEnum, variant: &Variant, structs: &Structs) -> eyre::Result<Value> {
+    // Namespaced errors are prefixed with the enum name, so that the selectors still match
+    let namespace = if has_attribute(&item.attrs, "namespaced") {
+        format!("{}::", item.ident)
+    } else {
+        String::new()
+    };
+
+    let inputs = variant
+        .fields
+        .iter()
+        .map(|field| param_abi(&field_name(field), &field.ty, structs))
+        .collect::<eyre::Result<Vec<_>>>()?;
+
+    Ok(json!({
+        "type": "error",
+        "name": format!("{}{}", namespace, variant.ident),
+        "inputs": inputs,
+    }))
+}
+
+fn param_abi(name: &str, ty: &Type, structs: &Structs) -> eyre::Result<Value> {
+    let (sol_type, components) = sol_type(ty, structs)?;
+    Ok(param_json(name, sol_type, components))
+}
+
+fn param_json(name: &str, sol_type: String, components: Option<Vec<Value>>) -> Value {
+    let mut param = json!({ "name": name, "type": sol_type });
+    if let Some(components) = components {
+        param["components"] = Value::Array(components);
+    }
+
+    param
+}
+
+// Unnamed fields have an empty name
+pub(crate) fn field_name(field: &Field) -> String {
+    field
+        .ident
+        .as_ref()
+        .map(|i| i.to_string())
+        .unwrap_or_default()
+}
+
+// Returns the Solidity type of a rust type, along with its components when it is a tuple or a
+// struct (or an array of them). Types are mapped like in `contract-derive`, and the remaining
+// ones must be structs of the contract.
+fn sol_type(ty: &Type, structs: &Structs) -> eyre::Result<(String, Option<Vec<Value>>)> {
+    if let Ok(sol_type) = rust_type_to_sol_type(ty) {
+        return Ok(dyn_sol_type(&sol_type));
+    }
+
+    match ty {
+        Type::Path(type_path) => {
+            let segment = type_path
+                .path
+                .segments
+                .last()
+                .ok_or_else(|| eyre::eyre!("Empty type path"))?;
+            let type_name = segment.ident.to_string();
+
+            if let Some(item) = structs.get(&type_name) {
+                let components = item
+                    .fields
+                    .iter()
+                    .map(|field| param_abi(&field_name(field), &field.ty, structs))
+                    .collect::<eyre::Result<Vec<_>>>()?;
+                return Ok(("tuple".to_string(), Some(components)));
+            }
+
+            match (type_name.as_str(), type_args(&segment.arguments).as_slice()) {
+                ("Vec", [inner]) => {
+                    let (inner, components) = sol_type(inner, structs)?;
+                    Ok((format!("{}[]", inner), components))
+                }
+                _ => eyre::bail!("Unresolved type: {}", type_name),
+            }
+        }
+        Type::Array(array) => {
+            let size = match &array.len {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Int(size),
+                    ..
+                }) => size.base10_parse::<usize>()?,
+                _ => eyre::bail!("Invalid array size expression"),
+            };
+            let (inner, components) = sol_type(&array.elem, structs)?;
+            Ok((format!("{}[{}]", inner, size), components))
+        }
+        Type::Tuple(tuple) => {
+            let components = tuple
+                .elems
+                .iter()
+                .map(|ty| param_abi("", ty, structs))
+                .collect::<eyre::Result<Vec<_>>>()?;
+            Ok(("tuple".to_string(), Some(components)))
+        }
+        _ => eyre::bail!("Unsupported type"),
+    }
+}
+
+// ABI type of a mapped type, whose tuples are described by their components
+fn dyn_sol_type(sol_type: &DynSolType) -> (String, Option<Vec<Value>>) {
+    match sol_type {
+        DynSolType::Tuple(types) => {
+            let components = types
+                .iter()
+                .map(|ty| {
+                    let (sol_type, components) = dyn_sol_type(ty);
+                    param_json("", sol_type, components)
+                })
+                .collect();
+            ("tuple".to_string(), Some(components))
+        }
+        DynSolType::Array(inner) => {
+            let (inner, components) = dyn_sol_type(inner);
+            (format!("{}[]", inner), components)
+        }
+        DynSolType::FixedArray(inner, size) => {
+            let (inner, components) = dyn_sol_type(inner);
+            (format!("{}[{}]", inner, size), components)
+        }
+        other => (other.sol_type_name().into_owned(), None),
+    }
+}
+
+pub(crate) fn type_args(arguments: &PathArguments) -> Vec<&Type> {
+    match arguments {
+        PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+// Check if attributes contain `#[name]`
+pub(crate) fn has_attribute(attrs: &[Attribute], name: &str) -> bool {
+    attrs
+        .iter()
+        .any(|attr| attr.path.segments.len() == 1 && attr.path.segments[0].ident == name)
+}
+
+// Check if attributes contain `#[derive(name)]`
+pub(crate) fn derives(attrs: &[Attribute], name: &str) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("derive"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .any(|nested| match nested {
+            NestedMeta::Meta(Meta::Path(path)) => {
+                path.segments.last().is_some_and(|s| s.ident == name)
+            }
+            _ => false,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abi_from_source(source: &str) -> Value {
+        Value::Array(abi_from_files(&[syn::parse_file(source).unwrap()]))
+    }
+
+    #[test]
+    fn test_function_abi() {
+        let abi = abi_from_source(
+            r#"
+            #[contract]
+            impl Token {
+                pub fn new(owner: Address) -> Self { todo!() }
+                pub fn balance_of(&self, owner: Address) -> U256 { todo!() }
+                pub fn transfer(&mut self, to: Address, amount: U256) -> Result<bool, TokenError> { todo!() }
+                #[payable]
+                pub fn mint(&mut self, to: Address) -> Option<(U256, Vec<B32>)> { todo!() }
+                fn helper(&self) {}
+                #[receive]
+                fn on_receive(&mut self) {}
+            }
+            "#,
+        );
+
+        assert_eq!(
+            abi,
+            json!([
+                {
+                    "type": "constructor",
+                    "inputs": [{ "name": "owner", "type": "address" }],
+                    "stateMutability": "payable",
+                },
+                {
+                    "type": "function",
+                    "name": "balance_of",
+                    "inputs": [{ "name": "owner", "type": "address" }],
+                    "outputs": [{ "name": "", "type": "uint256" }],
+                    "stateMutability": "view",
+                },
+                {
+                    "type": "function",
+                    "name": "transfer",
+                    "inputs": [
+                        { "name": "to", "type": "address" },
+                        { "name": "amount", "type": "uint256" },
+                    ],
+                    "outputs": [{ "name": "", "type": "bool" }],
+                    "stateMutability": "nonpayable",
+                },
+                {
+                    "type": "function",
+                    "name": "mint",
+                    "inputs": [{ "name": "to", "type": "address" }],
+                    "outputs": [
+                        { "name": "", "type": "uint256" },
+                        { "name": "", "type": "bytes32[]" },
+                    ],
+                    "stateMutability": "payable",
+                },
+                { "type": "receive", "stateMutability": "payable" },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_event_and_error_abi() {
+        let abi = abi_from_source(
+            r#"
+            #[derive(Event)]
+            pub struct Transfer {
+                #[indexed]
+                pub from: Address,
+                pub amount: U256,
+            }
+
+            #[derive(Event)]
+            #[anonymous]
+            pub struct Checkpoint {
+                #[indexed]
+                pub id: U256,
+            }
+
+            #[derive(Error)]
+            pub enum TokenError {
+                OnlyOwner,
+                InsufficientBalance(U256),
+            }
+            "#,
+        );
+
+        assert_eq!(
+            abi,
+            json!([
+                {
+                    "type": "event",
+                    "name": "Transfer",
+                    "inputs": [
+                        { "name": "from", "type": "address", "indexed": true },
+                        { "name": "amount", "type": "uint256", "indexed": false },
+                    ],
+                    "anonymous": false,
+                },
+                {
+                    "type": "event",
+                    "name": "Checkpoint",
+                    "inputs": [{ "name": "id", "type": "uint256", "indexed": true }],
+                    "anonymous": true,
+                },
+                { "type": "error", "name": "OnlyOwner", "inputs": [] },
+                {
+                    "type": "error",
+                    "name": "InsufficientBalance",
+                    "inputs": [{ "name": "", "type": "uint256" }],
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_struct_params_abi() {
+        let abi = abi_from_source(
+            r#"
+            #[derive(SolStruct)]
+            pub struct Limit {
+                pub token: Address,
+                pub max: U256,
+            }
+
+            #[derive(Event)]
+            pub struct LimitChecked {
+                pub limit: Limit,
+                pub pair: (U256, Limit),
+            }
+
+            #[derive(Error)]
+            pub enum LimitError {
+                ExceedsLimit { limit: Limit, amount: U256 },
+            }
+            "#,
+        );
+
+        let limit = json!([
+            { "name": "token", "type": "address" },
+            { "name": "max", "type": "uint256" },
+        ]);
+        assert_eq!(
+            abi,
+            json!([
+                {
+                    "type": "event",
+                    "name": "LimitChecked",
+                    "inputs": [
+                        { "name": "limit", "type": "tuple", "components": limit, "indexed": false },
+                        {
+                            "name": "pair",
+                            "type": "tuple",
+                            "components": [
+                                { "name": "", "type": "uint256" },
+                                { "name": "", "type": "tuple", "components": limit },
+                            ],
+                            "indexed": false,
+                        },
+                    ],
+                    "anonymous": false,
+                },
+                {
+                    "type": "error",
+                    "name": "ExceedsLimit",
+                    "inputs": [
+                        { "name": "limit", "type": "tuple", "components": limit },
+                        { "name": "amount", "type": "uint256" },
+                    ],
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_struct_arrays_and_unresolved_types_abi() {
+        let files = [
+            syn::parse_file(
+                r#"
+                use other_crate::Quote;
+
+                #[contract]
+                impl Vault {
+                    pub fn set_limits(&mut self, limits: Vec<Limit>, fee_bps: u16) {}
+                    pub fn quote(&self) -> Quote { todo!() }
+                    pub fn pairs(&self) -> [(Address, Limit); 2] { todo!() }
+                }
+
+                #[derive(Error)]
+                pub enum VaultError {
+                    Paused,
+                    StaleQuote(Quote),
+                }
+                "#,
+            )
+            .unwrap(),
+            // Structs may live in other modules of the contract
+            syn::parse_file(
+                r#"
+                pub mod types {
+                    #[derive(SolStruct)]
+                    pub struct Limit {
+                        pub token: Address,
+                        pub max: U256,
+                    }
+                }
+                "#,
+            )
+            .unwrap(),
+        ];
+
+        let limit = json!([
+            { "name": "token", "type": "address" },
+            { "name": "max", "type": "uint256" },
+        ]);
+        // Entries using `Quote`, which is defined in another crate, are left out
+        assert_eq!(
+            Value::Array(abi_from_files(&files)),
+            json!([
+                {
+                    "type": "function",
+                    "name": "set_limits",
+                    "inputs": [
+                        { "name": "limits", "type": "tuple[]", "components": limit },
+                        { "name": "fee_bps", "type": "uint16" },
+                    ],
+                    "outputs": [],
+                    "stateMutability": "nonpayable",
+                },
+                {
+                    "type": "function",
+                    "name": "pairs",
+                    "inputs": [],
+                    "outputs": [{
+                        "name": "",
+                        "type": "tuple[2]",
+                        "components": [
+                            { "name": "", "type": "address" },
+                            { "name": "", "type": "tuple", "components": limit },
+                        ],
+                    }],
+                    "stateMutability": "view",
+                },
+                { "type": "error", "name": "Paused", "inputs": [] },
+            ])
+        );
+    }
+}
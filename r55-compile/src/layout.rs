@@ -0,0 +1,458 @@
+use r55_types::{packed_bytes, rust_type_to_sol_type, LayoutBuilder};
+use serde_json::{json, Value};
+use syn::{Item, ItemStruct, Type};
+
+use crate::{
+    abi::{derived_structs, field_name, has_attribute, parse_sources, type_args, Structs},
+    compile::Contract,
+};
+
+// Storage footprint of a type, mirroring the `StorageLayout` impls of `eth-riscv-runtime`
+struct StorageType {
+    label: String,
+    bytes: usize,
+    slots: usize,
+    members: Option<Vec<Value>>,
+}
+
+impl StorageType {
+    // A value that may share its slot with other small values
+    fn word(label: String, bytes: usize) -> Self {
+        Self {
+            label,
+            bytes,
+            slots: 1,
+            members: None,
+        }
+    }
+
+    // A value that starts and ends on its own slots
+    fn slots(label: String, slots: usize, members: Option<Vec<Value>>) -> Self {
+        Self {
+            label,
+            bytes: 32,
+            slots,
+            members,
+        }
+    }
+}
+
+// Generate a solc-like storage layout of a contract, based on its source code
+pub fn generate_storage_layout(contract: &Contract) -> eyre::Result<Value> {
+    let files = parse_sources(&contract.path.join("src"))?;
+    layout_from_files(&files)
+}
+
+// The `#[storage]` struct is defined in the first file (`lib.rs`)
+fn layout_from_files(files: &[syn::File]) -> eyre::Result<Value> {
+    let structs = derived_structs(files, "StorageLayout");
+
+    let storage = files.first().and_then(|file| {
+        file.items.iter().find_map(|item| match item {
+            Item::Struct(item_struct) if has_attribute(&item_struct.attrs, "storage") => {
+                Some(item_struct)
+            }
+            _ => None,
+        })
+    });
+
+    let entries = match storage {
+        Some(item) => struct_layout(item, &structs)?.0,
+        None => Vec::new(),
+    };
+
+    Ok(json!({ "storage": entries }))
+}
+
+fn struct_layout(item: &ItemStruct, structs: &Structs) -> eyre::Result<(Vec<Value>, usize)> {
+    let fields = item
+        .fields
+        .iter()
+        .map(|field| (field_name(field), &field.ty));
+    fields_layout(fields, structs)
+}
+
+// Lays out the fields following Solidity's packing rules, like the runtime does.
+// Returns the entry of each field, along with the number of slots used.
+// Fails on the first field of an unsupported type, as the slots of the next ones would be unknown.
+fn fields_layout<'a>(
+    fields: impl Iterator<Item = (String, &'a Type)>,
+    structs: &Structs,
+) -> eyre::Result<(Vec<Value>, usize)> {
+    let mut entries = Vec::new();
+    let mut builder = LayoutBuilder::new();
+
+    for (label, ty) in fields {
+        let storage_type = storage_type(ty, structs)
+            .map_err(|e| eyre::eyre!("Unable to lay out `{}`: {}", label, e))?;
+        let (slot, offset) = builder.place(storage_type.bytes, storage_type.slots);
+
+        let mut entry = json!({
+            "label": label,
+            "slot": slot.to_string(),
+            "offset": offset,
+            "type": storage_type.label,
+        });
+        if let Some(members) = storage_type.members {
+            entry["members"] = Value::Array(members);
+        }
+        entries.push(entry);
+    }
+
+    Ok((entries, builder.slots()))
+}
+
+fn storage_type(ty: &Type, structs: &Structs) -> eyre::Result<StorageType> {
+    match ty {
+        Type::Path(type_path) => {
+            let segment = type_path
+                .path
+                .segments
+                .last()
+                .ok_or_else(|| eyre::eyre!("Empty type path"))?;
+            let type_name = segment.ident.to_string();
+
+            if let Some(item) = structs.get(&type_name) {
+                let (members, slots) = struct_layout(item, structs)?;
+                let label = format!("struct {}", type_name);
+                return Ok(StorageType::slots(label, slots, Some(members)));
+            }
+
+            match (type_name.as_str(), type_args(&segment.arguments).as_slice()) {
+                ("Slot", [value]) => {
+                    let (label, bytes) = word_type(value)?;
+                    Ok(StorageType::word(label, bytes))
+                }
+                ("Mapping", [key, value]) => {
+                    let (key, _) = word_type(key)?;
+                    let value = storage_type(value, structs)?.label;
+                    Ok(StorageType::slots(
+                        format!("mapping({} => {})", key, value),
+                        1,
+                        None,
+                    ))
+                }
+                // Dynamic values keep their length in the slot, and their data elsewhere
+                ("StorageVec", [value]) => {
+                    let (value, _) = word_type(value)?;
+                    Ok(StorageType::slots(format!("{}[]", value), 1, None))
+                }
+                ("StorageString", []) => Ok(StorageType::slots("string".to_string(), 1, None)),
+                ("StorageBytes", []) => Ok(StorageType::slots("bytes".to_string(), 1, None)),
+                // Enumerable types follow the layout of OpenZeppelin's structs
+                ("EnumerableSet", [value]) => {
+                    let (value, _) = word_type(value)?;
+                    let label = format!("struct EnumerableSet({})", value);
+                    Ok(StorageType::slots(label, 2, Some(enumerable_set_members())))
+                }
+                ("EnumerableMap", [key, value]) => {
+                    let ((key, _), (value, _)) = (word_type(key)?, word_type(value)?);
+                    let label = format!("struct EnumerableMap({} => {})", key, value);
+                    let members = vec![
+                        json!({
+                            "label": "_keys",
+                            "slot": "0",
+                            "offset": 0,
+                            "type": format!("struct EnumerableSet({})", key),
+                            "members": enumerable_set_members(),
+                        }),
+                        json!({
+                            "label": "_values",
+                            "slot": "2",
+                            "offset": 0,
+                            "type": "mapping(bytes32 => bytes32)",
+                        }),
+                    ];
+                    Ok(StorageType::slots(label, 3, Some(members)))
+                }
+                // Transient values don't use storage, but still reserve a whole slot
+                ("TransientSlot", [value]) => {
+                    let (value, _) = word_type(value)?;
+                    Ok(StorageType::slots(format!("transient {}", value), 1, None))
+                }
+                ("TransientMapping", [key, value]) => {
+                    let (key, _) = word_type(key)?;
+                    let (value, _) = word_type(value)?;
+                    let label = format!("transient mapping({} => {})", key, value);
+                    Ok(StorageType::slots(label, 1, None))
+                }
+                _ => eyre::bail!("Unsupported storage type: {}", type_name),
+            }
+        }
+        Type::Array(array) => {
+            let size = match &array.len {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Int(size),
+                    ..
+                }) => size.base10_parse::<usize>()?,
+                _ => eyre::bail!("Invalid array size expression"),
+            };
+            let elem = storage_type(&array.elem, structs)?;
+
+            // Elements smaller than a slot are packed together
+            let slots = if elem.bytes < 32 {
+                let per_slot = 32 / elem.bytes;
+                size.div_ceil(per_slot)
+            } else {
+                size * elem.slots
+            };
+            let label = format!("{}[{}]", elem.label, size);
+            Ok(StorageType::slots(label, slots, elem.members))
+        }
+        Type::Tuple(tuple) => {
+            let fields = tuple
+                .elems
+                .iter()
+                .enumerate()
+                .map(|(i, ty)| (i.to_string(), ty));
+            let (members, slots) = fields_layout(fields, structs)?;
+            let labels = members
+                .iter()
+                .map(|member| member["type"].as_str().unwrap_or_default())
+                .collect::<Vec<_>>();
+            let label = format!("tuple({})", labels.join(","));
+            Ok(StorageType::slots(label, slots, Some(members)))
+        }
+        _ => eyre::bail!("Unsupported storage type"),
+    }
+}
+
+// Values of enumerable types are stored as full words, regardless of their type
+fn enumerable_set_members() -> Vec<Value> {
+    vec![
+        json!({ "label": "_values", "slot": "0", "offset": 0, "type": "bytes32[]" }),
+        json!({
+            "label": "_positions",
+            "slot": "1",
+            "offset": 0,
+            "type": "mapping(bytes32 => uint256)",
+        }),
+    ]
+}
+
+// Returns the Solidity type of a value that fits in a slot, along with its size in bytes
+fn word_type(ty: &Type) -> eyre::Result<(String, usize)> {
+    let sol_type = rust_type_to_sol_type(ty).map_err(|e| eyre::eyre!(e))?;
+    let type_name = sol_type.sol_type_name().into_owned();
+    match packed_bytes(&sol_type) {
+        Some(bytes) => Ok((type_name, bytes)),
+        None => eyre::bail!("Unsupported storage value type: {}", type_name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout_from_source(source: &str) -> Value {
+        layout_from_files(&[syn::parse_file(source).unwrap()]).unwrap()
+    }
+
+    #[test]
+    fn test_unpacked_layout() {
+        let layout = layout_from_source(
+            r#"
+            #[storage]
+            pub struct ERC20 {
+                total_supply: Slot<U256>,
+                allowance_of: Mapping<Address, Mapping<Address, Slot<U256>>>,
+                owner: Slot<Address>,
+                name: StorageString,
+                holders: StorageVec<Address>,
+                pending: TransientSlot<U256>,
+            }
+            "#,
+        );
+
+        assert_eq!(
+            layout,
+            json!({
+                "storage": [
+                    { "label": "total_supply", "slot": "0", "offset": 0, "type": "uint256" },
+                    {
+                        "label": "allowance_of",
+                        "slot": "1",
+                        "offset": 0,
+                        "type": "mapping(address => mapping(address => uint256))",
+                    },
+                    { "label": "owner", "slot": "2", "offset": 0, "type": "address" },
+                    { "label": "name", "slot": "3", "offset": 0, "type": "string" },
+                    { "label": "holders", "slot": "4", "offset": 0, "type": "address[]" },
+                    { "label": "pending", "slot": "5", "offset": 0, "type": "transient uint256" },
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_packed_layout() {
+        let layout = layout_from_source(
+            r#"
+            #[derive(StorageLayout)]
+            pub struct Position {
+                amount: Slot<U256>,
+                owner: Slot<Address>,
+                locked: Slot<bool>,
+            }
+
+            #[storage]
+            pub struct Vault {
+                admin: Slot<Address>,
+                paused: Slot<bool>,
+                fee_bps: Slot<u16>,
+                limits: [Slot<U128>; 3],
+                pair: (Slot<bool>, Slot<U256>),
+                positions: Mapping<Address, Position>,
+            }
+            "#,
+        );
+
+        let position = json!([
+            { "label": "amount", "slot": "0", "offset": 0, "type": "uint256" },
+            { "label": "owner", "slot": "1", "offset": 0, "type": "address" },
+            { "label": "locked", "slot": "1", "offset": 20, "type": "bool" },
+        ]);
+        assert_eq!(
+            layout,
+            json!({
+                "storage": [
+                    { "label": "admin", "slot": "0", "offset": 0, "type": "address" },
+                    { "label": "paused", "slot": "0", "offset": 20, "type": "bool" },
+                    { "label": "fee_bps", "slot": "0", "offset": 21, "type": "uint16" },
+                    { "label": "limits", "slot": "1", "offset": 0, "type": "uint128[3]" },
+                    {
+                        "label": "pair",
+                        "slot": "3",
+                        "offset": 0,
+                        "type": "tuple(bool,uint256)",
+                        "members": [
+                            { "label": "0", "slot": "0", "offset": 0, "type": "bool" },
+                            { "label": "1", "slot": "1", "offset": 0, "type": "uint256" },
+                        ],
+                    },
+                    {
+                        "label": "positions",
+                        "slot": "5",
+                        "offset": 0,
+                        "type": "mapping(address => struct Position)",
+                    },
+                ]
+            })
+        );
+
+        // Struct members are relative to the slot where the struct starts
+        let layout = layout_from_source(
+            r#"
+            #[derive(StorageLayout)]
+            pub struct Position {
+                amount: Slot<U256>,
+                owner: Slot<Address>,
+                locked: Slot<bool>,
+            }
+
+            #[storage]
+            pub struct Vault {
+                paused: Slot<bool>,
+                position: Position,
+            }
+            "#,
+        );
+        assert_eq!(
+            layout["storage"][1],
+            json!({
+                "label": "position",
+                "slot": "1",
+                "offset": 0,
+                "type": "struct Position",
+                "members": position,
+            })
+        );
+    }
+
+    #[test]
+    fn test_enumerable_layout() {
+        let layout = layout_from_source(
+            r#"
+            #[storage]
+            pub struct Allowlist {
+                admin: Slot<Address>,
+                members: EnumerableSet<Address>,
+                quotas: EnumerableMap<Address, U256>,
+                paused: Slot<bool>,
+            }
+            "#,
+        );
+
+        let set_members = json!([
+            { "label": "_values", "slot": "0", "offset": 0, "type": "bytes32[]" },
+            {
+                "label": "_positions",
+                "slot": "1",
+                "offset": 0,
+                "type": "mapping(bytes32 => uint256)",
+            },
+        ]);
+        assert_eq!(
+            layout,
+            json!({
+                "storage": [
+                    { "label": "admin", "slot": "0", "offset": 0, "type": "address" },
+                    {
+                        "label": "members",
+                        "slot": "1",
+                        "offset": 0,
+                        "type": "struct EnumerableSet(address)",
+                        "members": set_members,
+                    },
+                    {
+                        "label": "quotas",
+                        "slot": "3",
+                        "offset": 0,
+                        "type": "struct EnumerableMap(address => uint256)",
+                        "members": [
+                            {
+                                "label": "_keys",
+                                "slot": "0",
+                                "offset": 0,
+                                "type": "struct EnumerableSet(address)",
+                                "members": set_members,
+                            },
+                            {
+                                "label": "_values",
+                                "slot": "2",
+                                "offset": 0,
+                                "type": "mapping(bytes32 => bytes32)",
+                            },
+                        ],
+                    },
+                    { "label": "paused", "slot": "6", "offset": 0, "type": "bool" },
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_unsupported_field_fails() {
+        let source = r#"
+            #[storage]
+            pub struct Registry {
+                owner: Slot<Address>,
+                oracle: other_crate::Oracle,
+                paused: Slot<bool>,
+            }
+        "#;
+        let err = layout_from_files(&[syn::parse_file(source).unwrap()]).unwrap_err();
+        assert!(err.to_string().contains("`oracle`"), "{}", err);
+
+        // Unsupported values are reported on the field that holds them
+        let source = r#"
+            #[storage]
+            pub struct Registry {
+                owner: Slot<Address>,
+                names: Mapping<Address, Slot<String>>,
+            }
+        "#;
+        let err = layout_from_files(&[syn::parse_file(source).unwrap()]).unwrap_err();
+        assert!(err.to_string().contains("`names`"), "{}", err);
+    }
+}
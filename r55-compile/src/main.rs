@@ -1,11 +1,17 @@
+mod abi;
+use abi::generate_abi;
+
 mod compile;
 use compile::{find_r55_contracts, sort_r55_contracts};
 
 mod deployable;
 use deployable::generate_deployable;
 
+mod layout;
+use layout::generate_storage_layout;
+
 use std::{fs, path::Path};
-use tracing::info;
+use tracing::{info, warn};
 
 fn main() -> eyre::Result<()> {
     // Initialize logging
@@ -54,6 +60,33 @@ fn main() -> eyre::Result<()> {
         let deploy_bytecode = contract.compile_r55()?;
         let deploy_path = output_dir.join(format!("{}.bin", contract.name.package));
         fs::write(deploy_path, deploy_bytecode)?;
+
+        // Generate the ABI and save it next to the bytecode
+        match generate_abi(&contract) {
+            Ok(abi) => {
+                let abi_path = output_dir.join(format!("{}.abi.json", contract.name.package));
+                fs::write(abi_path, serde_json::to_string_pretty(&abi)?)?;
+            }
+            Err(e) => warn!(
+                "Unable to generate the ABI of {}: {}",
+                contract.name.ident, e
+            ),
+        }
+
+        // Generate the storage layout and save it next to the bytecode
+        match generate_storage_layout(&contract) {
+            Ok(layout) => {
+                let layout_path =
+                    output_dir.join(format!("{}.storage.json", contract.name.package));
+                fs::write(layout_path, serde_json::to_string_pretty(&layout)?)?;
+            }
+            Err(e) => {
+                warn!(
+                    "Unable to generate the storage layout of {}: {}",
+                    contract.name.ident, e
+                )
+            }
+        }
     }
 
     Ok(())
@@ -0,0 +1,87 @@
+/// Places values in storage following Solidity's packing rules: values smaller than a slot
+/// share it with their neighbours as long as they fit, the others start and end on their own
+/// slots.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LayoutBuilder {
+    slot: usize,
+    offset: usize,
+}
+
+impl LayoutBuilder {
+    pub const fn new() -> Self {
+        Self { slot: 0, offset: 0 }
+    }
+
+    /// Returns the `(slot, offset)` of the next value, which uses `bytes` bytes within a slot
+    /// (`32` for types that use whole slots) and `slots` consecutive slots.
+    pub const fn place(&mut self, bytes: usize, slots: usize) -> (usize, usize) {
+        if bytes < 32 {
+            // Small values share a slot, as long as they fit in it
+            if self.offset + bytes > 32 {
+                self.slot += 1;
+                self.offset = 0;
+            }
+            let position = (self.slot, self.offset);
+            self.offset += bytes;
+            position
+        } else {
+            // Other values start and end on their own slots
+            if self.offset > 0 {
+                self.slot += 1;
+                self.offset = 0;
+            }
+            let position = (self.slot, 0);
+            self.slot += slots;
+            position
+        }
+    }
+
+    /// Number of slots used by the values placed so far.
+    pub const fn slots(&self) -> usize {
+        if self.offset > 0 {
+            self.slot + 1
+        } else {
+            self.slot
+        }
+    }
+}
+
+/// Computes a Solidity-style storage layout from the `(bytes, slots)` of each field.
+/// Returns the `(slot, offset)` of each field, along with the number of slots used.
+pub const fn compute_layout<const N: usize>(
+    fields: [(usize, usize); N],
+) -> ([(usize, usize); N], usize) {
+    let mut positions = [(0, 0); N];
+    let mut builder = LayoutBuilder::new();
+
+    let mut i = 0;
+    while i < N {
+        let (bytes, slots) = fields[i];
+        positions[i] = builder.place(bytes, slots);
+        i += 1;
+    }
+
+    (positions, builder.slots())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_layout() {
+        // address, bool, uint16, uint128[3], uint256, bool
+        let (positions, slots) =
+            compute_layout([(20, 1), (1, 1), (2, 1), (32, 2), (32, 1), (1, 1)]);
+        assert_eq!(
+            positions,
+            [(0, 0), (0, 20), (0, 21), (1, 0), (3, 0), (4, 0)]
+        );
+        assert_eq!(slots, 5);
+
+        // Values that don't fit in the remaining bytes move to the next slot
+        let (positions, slots) = compute_layout([(16, 1), (8, 1), (16, 1)]);
+        assert_eq!(positions, [(0, 0), (0, 16), (1, 0)]);
+        assert_eq!(slots, 2);
+    }
+}
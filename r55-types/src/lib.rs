@@ -0,0 +1,13 @@
+//! Type information shared by the runtime, the contract macros and the compiler, so that they
+//! agree on how rust types map to Solidity ones and on how they are laid out in storage.
+#![no_std]
+
+extern crate alloc;
+
+mod layout;
+pub use layout::{compute_layout, LayoutBuilder};
+
+#[cfg(feature = "syn")]
+mod sol;
+#[cfg(feature = "syn")]
+pub use sol::{packed_bytes, rust_type_to_sol_type, DynSolType};
@@ -0,0 +1,300 @@
+use alloc::{boxed::Box, string::ToString, vec::Vec};
+pub use alloy_dyn_abi::DynSolType;
+use syn::Type;
+
+/// Converts a rust type to its Solidity equivalent. Structs, and any other type that isn't
+/// known by name, are left to the caller.
+pub fn rust_type_to_sol_type(ty: &Type) -> Result<DynSolType, &'static str> {
+    match ty {
+        Type::Path(type_path) => {
+            let path = &type_path.path;
+            let segment = path.segments.last().ok_or("Empty type path")?;
+            let ident = &segment.ident;
+            let type_name = ident.to_string();
+
+            match type_name.as_str() {
+                // Fixed-size types
+                "Address" => Ok(DynSolType::Address),
+                "Function" => Ok(DynSolType::Function),
+                "bool" | "Bool" => Ok(DynSolType::Bool),
+                "String" | "str" => Ok(DynSolType::String),
+                "Bytes" => Ok(DynSolType::Bytes),
+                // Alloy fixed-size bytes aliases, named after their size in bits
+                "B64" => Ok(DynSolType::FixedBytes(8)),
+                "B128" => Ok(DynSolType::FixedBytes(16)),
+                "B256" => Ok(DynSolType::FixedBytes(32)),
+                // Fixed-size bytes
+                b if b.starts_with('B') => {
+                    let size: usize = b
+                        .trim_start_matches('B')
+                        .parse()
+                        .map_err(|_| "Invalid fixed bytes size")?;
+                    if size > 0 && size <= 32 {
+                        Ok(DynSolType::FixedBytes(size))
+                    } else {
+                        Err("Invalid fixed bytes size (between 1-32)")
+                    }
+                }
+                // Fixed-size unsigned integers, from alloy or rust primitives
+                u if u.starts_with('U') || u.starts_with('u') => {
+                    let size: usize = u[1..].parse().map_err(|_| "Invalid uint size")?;
+                    if size > 0 && size <= 256 && size % 8 == 0 {
+                        Ok(DynSolType::Uint(size))
+                    } else {
+                        Err("Invalid uint size (multiple of 8 + leq 256)")
+                    }
+                }
+                // Fixed-size signed integers, from alloy or rust primitives
+                i if i.starts_with('I') || i.starts_with('i') => {
+                    let size: usize = i[1..].parse().map_err(|_| "Invalid int size")?;
+                    if size > 0 && size <= 256 && size % 8 == 0 {
+                        Ok(DynSolType::Int(size))
+                    } else {
+                        Err("Invalid int size (must be multiple of 8, max 256)")
+                    }
+                }
+                // Handle vecs + generic fixed-size bytes
+                _ => {
+                    if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                        match type_name.as_str() {
+                            "Vec" => {
+                                let inner = args.args.first().ok_or("Empty Vec type argument")?;
+                                if let syn::GenericArgument::Type(inner_ty) = inner {
+                                    let inner_sol_type = rust_type_to_sol_type(inner_ty)?;
+                                    Ok(DynSolType::Array(Box::new(inner_sol_type)))
+                                } else {
+                                    Err("Invalid Vec type argument")
+                                }
+                            }
+                            "FixedBytes" => {
+                                let size = match args.args.first() {
+                                    Some(syn::GenericArgument::Const(syn::Expr::Lit(lit))) => {
+                                        match &lit.lit {
+                                            syn::Lit::Int(size) => size
+                                                .base10_parse::<usize>()
+                                                .map_err(|_| "Invalid fixed bytes size")?,
+                                            _ => return Err("Invalid fixed bytes size"),
+                                        }
+                                    }
+                                    _ => return Err("Invalid FixedBytes type argument"),
+                                };
+                                if size > 0 && size <= 32 {
+                                    Ok(DynSolType::FixedBytes(size))
+                                } else {
+                                    Err("Invalid fixed bytes size (between 1-32)")
+                                }
+                            }
+                            _ => Err("Unsupported generic type"),
+                        }
+                    } else {
+                        Err("Unsupported type")
+                    }
+                }
+            }
+        }
+        Type::Array(array) => {
+            let inner_sol_type = rust_type_to_sol_type(&array.elem)?;
+            if let syn::Expr::Lit(lit) = &array.len {
+                if let syn::Lit::Int(size) = &lit.lit {
+                    let size: usize = size
+                        .base10_digits()
+                        .parse()
+                        .map_err(|_| "Invalid array size")?;
+                    Ok(DynSolType::FixedArray(Box::new(inner_sol_type), size))
+                } else {
+                    Err("Invalid array size literal")
+                }
+            } else {
+                Err("Invalid array size expression")
+            }
+        }
+        Type::Tuple(tuple) => {
+            let inner_types = tuple
+                .elems
+                .iter()
+                .map(rust_type_to_sol_type)
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(DynSolType::Tuple(inner_types))
+        }
+        _ => Err("Unsupported type"),
+    }
+}
+
+/// Number of bytes used by a value of a Solidity type when packed in a storage slot, or `None`
+/// when the type doesn't fit in a single slot.
+pub fn packed_bytes(sol_type: &DynSolType) -> Option<usize> {
+    match sol_type {
+        DynSolType::Address => Some(20),
+        DynSolType::Bool => Some(1),
+        DynSolType::FixedBytes(size) => Some(*size),
+        DynSolType::Uint(bits) | DynSolType::Int(bits) => Some(bits / 8),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_rust_to_sol_basic_types() {
+        let test_cases = vec![
+            (parse_quote!(Address), DynSolType::Address),
+            (parse_quote!(Function), DynSolType::Function),
+            (parse_quote!(bool), DynSolType::Bool),
+            (parse_quote!(Bool), DynSolType::Bool),
+            (parse_quote!(String), DynSolType::String),
+            (parse_quote!(str), DynSolType::String),
+            (parse_quote!(Bytes), DynSolType::Bytes),
+        ];
+
+        for (rust_type, expected_sol_type) in test_cases {
+            assert_eq!(
+                rust_type_to_sol_type(&rust_type).unwrap(),
+                expected_sol_type
+            );
+        }
+    }
+
+    #[test]
+    fn test_rust_to_sol_fixed_bytes() {
+        let test_cases = vec![
+            (parse_quote!(B1), DynSolType::FixedBytes(1)),
+            (parse_quote!(B16), DynSolType::FixedBytes(16)),
+            (parse_quote!(B32), DynSolType::FixedBytes(32)),
+            (parse_quote!(B256), DynSolType::FixedBytes(32)),
+            (parse_quote!(FixedBytes<4>), DynSolType::FixedBytes(4)),
+        ];
+
+        for (rust_type, expected_sol_type) in test_cases {
+            assert_eq!(
+                rust_type_to_sol_type(&rust_type).unwrap(),
+                expected_sol_type
+            );
+        }
+
+        // Invalid cases
+        assert!(rust_type_to_sol_type(&parse_quote!(B0)).is_err());
+        assert!(rust_type_to_sol_type(&parse_quote!(B33)).is_err());
+        assert!(rust_type_to_sol_type(&parse_quote!(FixedBytes<33>)).is_err());
+    }
+
+    #[test]
+    fn test_rust_to_sol_integers() {
+        let test_cases = vec![
+            (parse_quote!(U8), DynSolType::Uint(8)),
+            (parse_quote!(U256), DynSolType::Uint(256)),
+            (parse_quote!(I8), DynSolType::Int(8)),
+            (parse_quote!(I256), DynSolType::Int(256)),
+            (parse_quote!(u16), DynSolType::Uint(16)),
+            (parse_quote!(i64), DynSolType::Int(64)),
+        ];
+
+        for (rust_type, expected_sol_type) in test_cases {
+            assert_eq!(
+                rust_type_to_sol_type(&rust_type).unwrap(),
+                expected_sol_type
+            );
+        }
+
+        // Invalid cases
+        assert!(rust_type_to_sol_type(&parse_quote!(U0)).is_err());
+        assert!(rust_type_to_sol_type(&parse_quote!(U257)).is_err());
+        assert!(rust_type_to_sol_type(&parse_quote!(U7)).is_err()); // Not multiple of 8
+        assert!(rust_type_to_sol_type(&parse_quote!(I0)).is_err());
+        assert!(rust_type_to_sol_type(&parse_quote!(I257)).is_err());
+        assert!(rust_type_to_sol_type(&parse_quote!(I7)).is_err()); // Not multiple of 8
+        assert!(rust_type_to_sol_type(&parse_quote!(usize)).is_err());
+    }
+
+    #[test]
+    fn test_rust_to_sol_arrays() {
+        // Dynamic arrays (Vec)
+        assert_eq!(
+            rust_type_to_sol_type(&parse_quote!(Vec<U256>)).unwrap(),
+            DynSolType::Array(Box::new(DynSolType::Uint(256)))
+        );
+
+        assert_eq!(
+            rust_type_to_sol_type(&parse_quote!(Vec<Bool>)).unwrap(),
+            DynSolType::Array(Box::new(DynSolType::Bool))
+        );
+
+        // Fixed-size arrays
+        assert_eq!(
+            rust_type_to_sol_type(&parse_quote!([U256; 5])).unwrap(),
+            DynSolType::FixedArray(Box::new(DynSolType::Uint(256)), 5)
+        );
+
+        assert_eq!(
+            rust_type_to_sol_type(&parse_quote!([Bool; 3])).unwrap(),
+            DynSolType::FixedArray(Box::new(DynSolType::Bool), 3)
+        );
+    }
+
+    #[test]
+    fn test_rust_to_sol_tuples() {
+        assert_eq!(
+            rust_type_to_sol_type(&parse_quote!((U256, Bool))).unwrap(),
+            DynSolType::Tuple(vec![DynSolType::Uint(256), DynSolType::Bool])
+        );
+
+        assert_eq!(
+            rust_type_to_sol_type(&parse_quote!((Address, B32, I128))).unwrap(),
+            DynSolType::Tuple(vec![
+                DynSolType::Address,
+                DynSolType::FixedBytes(32),
+                DynSolType::Int(128)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_rust_to_sol_nested_types() {
+        // Nested Vec
+        assert_eq!(
+            rust_type_to_sol_type(&parse_quote!(Vec<Vec<U256>>)).unwrap(),
+            DynSolType::Array(Box::new(DynSolType::Array(Box::new(DynSolType::Uint(256)))))
+        );
+
+        // Nested fixed array
+        assert_eq!(
+            rust_type_to_sol_type(&parse_quote!([[U256; 2]; 3])).unwrap(),
+            DynSolType::FixedArray(
+                Box::new(DynSolType::FixedArray(Box::new(DynSolType::Uint(256)), 2)),
+                3
+            )
+        );
+
+        // Nested tuple
+        assert_eq!(
+            rust_type_to_sol_type(&parse_quote!((U256, (Bool, Address)))).unwrap(),
+            DynSolType::Tuple(vec![
+                DynSolType::Uint(256),
+                DynSolType::Tuple(vec![DynSolType::Bool, DynSolType::Address])
+            ])
+        );
+    }
+
+    #[test]
+    fn test_rust_to_sol_invalid_types() {
+        // Invalid type names
+        assert!(rust_type_to_sol_type(&parse_quote!(InvalidType)).is_err());
+
+        // Invalid generic types
+        assert!(rust_type_to_sol_type(&parse_quote!(Option<U256>)).is_err());
+        assert!(rust_type_to_sol_type(&parse_quote!(Result<U256>)).is_err());
+    }
+
+    #[test]
+    fn test_packed_bytes() {
+        assert_eq!(packed_bytes(&DynSolType::Address), Some(20));
+        assert_eq!(packed_bytes(&DynSolType::Bool), Some(1));
+        assert_eq!(packed_bytes(&DynSolType::FixedBytes(4)), Some(4));
+        assert_eq!(packed_bytes(&DynSolType::Uint(128)), Some(16));
+        assert_eq!(packed_bytes(&DynSolType::Int(256)), Some(32));
+        assert_eq!(packed_bytes(&DynSolType::String), None);
+    }
+}
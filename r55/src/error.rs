@@ -4,7 +4,7 @@ use core::fmt;
 
 use alloy_primitives::{keccak256, Bytes};
 use revm::{
-    primitives::{EVMError, ExecutionResult, Log},
+    primitives::{EVMError, ExecutionResult, HaltReason, Log},
     Database, InMemoryDB,
 };
 use rvemu::exception::Exception;
@@ -124,17 +124,31 @@ where
         }
     }
 
+    /// Gas used by a transaction that was executed, but didn't succeed
+    pub fn gas_used(&self) -> Option<u64> {
+        match self {
+            Error::UnexpectedExecResult(result) => Some(result.gas_used()),
+            _ => None,
+        }
+    }
+
+    pub fn matches_out_of_gas(&self) -> bool {
+        matches!(
+            self,
+            Error::UnexpectedExecResult(ExecutionResult::Halt {
+                reason: HaltReason::OutOfGas(_),
+                ..
+            })
+        )
+    }
+
     pub fn matches_custom_error(&self, err: &'static str) -> bool {
         if let Error::UnexpectedExecResult(ExecutionResult::Revert {
             gas_used: _,
             output,
         }) = &self
         {
-            if keccak256(err)[..4].to_vec() != output[..4] {
-                return false;
-            }
-
-            true
+            output.starts_with(&error_selector(err))
         } else {
             false
         }
@@ -146,8 +160,7 @@ where
             output,
         }) = &self
         {
-            let err = Bytes::from(keccak256(err)[..4].to_vec());
-            if err != output[..4] {
+            if !output.starts_with(&error_selector(err)) {
                 return false;
             }
 
@@ -161,3 +174,15 @@ where
         }
     }
 }
+
+/// Returns the selector of a custom error signature, such as `InsufficientBalance(uint256)`.
+/// The parentheses can be omitted for errors without params.
+pub fn error_selector(err: &str) -> [u8; 4] {
+    let hash = if err.ends_with(')') {
+        keccak256(err)
+    } else {
+        keccak256(format!("{}()", err))
+    };
+
+    hash[..4].try_into().unwrap()
+}
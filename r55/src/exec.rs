@@ -1,6 +1,6 @@
 use alloy_core::primitives::{Keccak256, U32};
 use core::cell::RefCell;
-use eth_riscv_interpreter::setup_from_elf;
+use eth_riscv_interpreter::{memory_access, setup_from_elf, MemoryTracker};
 use eth_riscv_syscalls::Syscall;
 use revm::{
     handler::register::EvmHandler,
@@ -8,25 +8,25 @@ use revm::{
         CallInputs, CallScheme, CallValue, CreateInputs, CreateScheme, Host, InstructionResult,
         Interpreter, InterpreterAction, InterpreterResult, SharedMemory,
     },
-    primitives::{address, Address, Bytes, ExecutionResult, Log, Output, TransactTo, B256, U256},
+    primitives::{
+        address, Address, BlockEnv, Bytes, ExecutionResult, Log, Output, TransactTo, B256,
+        BLOCK_HASH_HISTORY, U256,
+    },
     Database, Evm, Frame, FrameOrResult, InMemoryDB,
 };
 use rvemu::{emulator::Emulator, exception::Exception};
-use std::{collections::BTreeMap, rc::Rc, sync::Arc};
+use std::{rc::Rc, sync::Arc};
 use tracing::{debug, info, trace, warn};
 
 use super::error::{Error, Result, TxResult};
-use super::gas;
+use super::gas::{self, GasSchedule};
 use super::syscall_gas;
 
 const R5_REST_OF_RAM_INIT: u64 = 0x80300000; // Defined at `r5-rust-rt.x`
 
-pub fn deploy_contract(
-    db: &mut InMemoryDB,
-    bytecode: Bytes,
-    encoded_args: Option<Vec<u8>>,
-) -> Result<Address> {
-    let init_code = if Some(&0xff) == bytecode.first() {
+/// Builds the initcode used to deploy a contract
+pub fn init_code(bytecode: Bytes, encoded_args: Option<Vec<u8>>) -> Bytes {
+    if Some(&0xff) == bytecode.first() {
         // Craft R55 initcode: [0xFF][codesize][bytecode][constructor_args]
         let codesize = U32::from(bytecode.len());
         debug!("[DEPLOY] BYTECODE SIZE: {}", codesize);
@@ -44,7 +44,25 @@ pub fn deploy_contract(
     } else {
         // do not modify bytecode for EVM contracts
         bytecode
-    };
+    }
+}
+
+/// Precomputes the address of a contract deployed by `deployer` with CREATE2
+pub fn create2_address(
+    deployer: Address,
+    salt: B256,
+    bytecode: Bytes,
+    encoded_args: Option<Vec<u8>>,
+) -> Address {
+    deployer.create2_from_code(salt, init_code(bytecode, encoded_args))
+}
+
+pub fn deploy_contract(
+    db: &mut InMemoryDB,
+    bytecode: Bytes,
+    encoded_args: Option<Vec<u8>>,
+) -> Result<Address> {
+    let init_code = init_code(bytecode, encoded_args);
 
     // Run CREATE tx
     let mut evm = Evm::builder()
@@ -82,21 +100,65 @@ pub fn deploy_contract(
     }
 }
 
+// Gas limit of the transactions run by `run_tx`
+const TX_GAS_LIMIT: u64 = 100_000_000;
+
 pub fn run_tx(
     db: &mut InMemoryDB,
     addr: &Address,
     calldata: Vec<u8>,
     caller: &Address,
+) -> Result<TxResult> {
+    transact(
+        db,
+        addr,
+        calldata,
+        caller,
+        BlockEnv::default(),
+        TX_GAS_LIMIT,
+    )
+}
+
+/// Runs a transaction in the given block, such as one with a number or `prevrandao`.
+pub fn run_tx_with_block(
+    db: &mut InMemoryDB,
+    addr: &Address,
+    calldata: Vec<u8>,
+    caller: &Address,
+    block: BlockEnv,
+) -> Result<TxResult> {
+    transact(db, addr, calldata, caller, block, TX_GAS_LIMIT)
+}
+
+/// Runs a transaction with the given gas limit, such as a small one to run out of gas quickly.
+pub fn run_tx_with_gas_limit(
+    db: &mut InMemoryDB,
+    addr: &Address,
+    calldata: Vec<u8>,
+    caller: &Address,
+    gas_limit: u64,
+) -> Result<TxResult> {
+    transact(db, addr, calldata, caller, BlockEnv::default(), gas_limit)
+}
+
+fn transact(
+    db: &mut InMemoryDB,
+    addr: &Address,
+    calldata: Vec<u8>,
+    caller: &Address,
+    block: BlockEnv,
+    gas_limit: u64,
 ) -> Result<TxResult> {
     let mut evm = Evm::builder()
         .with_db(db)
+        .modify_block_env(|env| *env = block)
         .modify_tx_env(|tx| {
             tx.caller = *caller;
             tx.transact_to = TransactTo::Call(*addr);
             tx.data = calldata.into();
             tx.value = U256::from(0);
             tx.gas_price = U256::from(42);
-            tx.gas_limit = 100_000_000;
+            tx.gas_limit = gas_limit;
         })
         .modify_cfg_env(|cfg| cfg.limit_contract_code_size = Some(usize::MAX))
         .append_handler_register(handle_register)
@@ -128,7 +190,8 @@ pub fn run_tx(
 #[derive(Debug)]
 struct RVEmu {
     emu: Emulator,
-    created_address: Option<Address>,
+    memory: MemoryTracker,
+    pending_call: bool,
 }
 
 fn riscv_context(frame: &Frame) -> Option<RVEmu> {
@@ -158,7 +221,8 @@ fn riscv_context(frame: &Frame) -> Option<RVEmu> {
     match setup_from_elf(code, calldata) {
         Ok(emu) => Some(RVEmu {
             emu,
-            created_address: None,
+            memory: MemoryTracker::default(),
+            pending_call: false,
         }),
         Err(err) => {
             warn!("Failed to setup from ELF: {err}");
@@ -213,14 +277,9 @@ pub fn handle_register<EXT, DB: Database>(handler: &mut EvmHandler<'_, EXT, DB>)
             old_handle(frame, memory, instraction_table, ctx)?
         };
 
-        // if action is return, pop the stack and potentially cache created address.
+        // if action is return, pop the stack.
         if result.is_return() {
-            let mut stack = call_stack.borrow_mut();
-            stack.pop();
-
-            if let Some(Some(parent)) = stack.last_mut() {
-                parent.created_address = frame.created_address()
-            }
+            call_stack.borrow_mut().pop();
         }
 
         debug!("=== [Frame-{}] {:#?}", depth, frame.interpreter().gas);
@@ -244,8 +303,19 @@ fn execute_riscv(
         rvemu.emu.cpu.pc,
     );
 
+    // When resuming after a call, its outcome (1 on success, 0 otherwise) has been pushed onto
+    // the stack, and is returned to the caller
+    if core::mem::take(&mut rvemu.pending_call) {
+        let status = interpreter.stack.pop().unwrap_or_default();
+        rvemu.emu.cpu.xregs.write(10, status.to::<u64>());
+    }
+
     let emu = &mut rvemu.emu;
-    emu.cpu.is_count = true;
+    let schedule = GasSchedule::latest();
+
+    // Gas of the RISC-V instructions executed since the last syscall, which is recorded
+    // before handling the next syscall.
+    let mut instruction_gas = 0_u64;
 
     let return_revert = |interpreter: &mut Interpreter, gas_used: u64| {
         let _ = interpreter.gas.record_cost(gas_used);
@@ -259,11 +329,29 @@ fn execute_riscv(
         })
     };
 
-    // Run emulator and capture ecalls
+    // Exceptional halts keep the gas spent so far, but the caller doesn't get the rest back
+    let return_halt = |interpreter: &mut Interpreter, result: InstructionResult| {
+        Ok(InterpreterAction::Return {
+            result: InterpreterResult {
+                result,
+                output: Bytes::new(),
+                gas: interpreter.gas,
+            },
+        })
+    };
+
+    // Run emulator, metering every instruction, and capture ecalls
     loop {
-        let run_result = emu.start();
+        // Charge the memory expansion caused by the next instruction, if any
+        if let Some((address, size)) = memory_access(emu) {
+            instruction_gas += memory_expansion_cost(&mut rvemu.memory, address, size);
+        }
+
+        let run_result = step(emu);
         match run_result {
             Err(Exception::EnvironmentCallFromMMode) => {
+                syscall_gas!(interpreter, core::mem::take(&mut instruction_gas));
+
                 let t0: u64 = emu.cpu.xregs.read(5);
 
                 let Ok(syscall) = Syscall::try_from(t0 as u8) else {
@@ -277,12 +365,6 @@ fn execute_riscv(
                         let ret_offset: u64 = emu.cpu.xregs.read(10);
                         let ret_size: u64 = emu.cpu.xregs.read(11);
 
-                        let r55_gas = r55_gas_used(&emu.cpu.inst_counter);
-                        debug!("> Total R55 gas: {}", r55_gas);
-
-                        // RETURN logs the gas of the whole risc-v instruction set
-                        syscall_gas!(interpreter, r55_gas);
-
                         let data_bytes = dram_slice(emu, ret_offset, ret_size)?;
                         trace!("> RETURN: {}", Bytes::from(data_bytes.to_vec()));
 
@@ -290,7 +372,7 @@ fn execute_riscv(
                             result: InterpreterResult {
                                 result: InstructionResult::Return,
                                 output: data_bytes.to_vec().into(),
-                                gas: interpreter.gas, // FIXME: gas is not correct
+                                gas: interpreter.gas,
                             },
                         });
                     }
@@ -330,6 +412,14 @@ fn execute_riscv(
                         }
                     }
                     Syscall::SStore => {
+                        // storage can't be modified within a static call
+                        if interpreter.is_static {
+                            return return_halt(
+                                interpreter,
+                                InstructionResult::StateChangeDuringStaticCall,
+                            );
+                        }
+
                         let key1: u64 = emu.cpu.xregs.read(10);
                         let key2: u64 = emu.cpu.xregs.read(11);
                         let key3: u64 = emu.cpu.xregs.read(12);
@@ -362,6 +452,49 @@ fn execute_riscv(
                             );
                         }
                     }
+                    Syscall::TLoad => {
+                        let key1: u64 = emu.cpu.xregs.read(10);
+                        let key2: u64 = emu.cpu.xregs.read(11);
+                        let key3: u64 = emu.cpu.xregs.read(12);
+                        let key4: u64 = emu.cpu.xregs.read(13);
+                        let key = U256::from_limbs([key1, key2, key3, key4]);
+
+                        syscall_gas!(interpreter, gas::TLOAD);
+                        let value = host.tload(interpreter.contract.target_address, key);
+                        debug!(
+                            "> TLOAD ({}) - Key: {:#02x}, Value: {}",
+                            interpreter.contract.target_address, key, value
+                        );
+                        write_u256(emu, value);
+                    }
+                    Syscall::TStore => {
+                        // transient storage can't be modified within a static call
+                        if interpreter.is_static {
+                            return return_halt(
+                                interpreter,
+                                InstructionResult::StateChangeDuringStaticCall,
+                            );
+                        }
+
+                        let key1: u64 = emu.cpu.xregs.read(10);
+                        let key2: u64 = emu.cpu.xregs.read(11);
+                        let key3: u64 = emu.cpu.xregs.read(12);
+                        let key4: u64 = emu.cpu.xregs.read(13);
+                        let key = U256::from_limbs([key1, key2, key3, key4]);
+
+                        let val1: u64 = emu.cpu.xregs.read(14);
+                        let val2: u64 = emu.cpu.xregs.read(15);
+                        let val3: u64 = emu.cpu.xregs.read(16);
+                        let val4: u64 = emu.cpu.xregs.read(17);
+                        let value = U256::from_limbs([val1, val2, val3, val4]);
+                        debug!(
+                            "> TSTORE ({}) - Key: {:#02x}, Value: {}",
+                            interpreter.contract.target_address, key, value
+                        );
+
+                        syscall_gas!(interpreter, gas::TSTORE);
+                        host.tstore(interpreter.contract.target_address, key, value);
+                    }
                     Syscall::ReturnDataSize => {
                         let size = interpreter.return_data_buffer.len();
                         debug!("> RETURNDATASIZE: {}", size);
@@ -372,6 +505,10 @@ fn execute_riscv(
                         let dest_offset = emu.cpu.xregs.read(10);
                         let offset = emu.cpu.xregs.read(11) as usize;
                         let size = emu.cpu.xregs.read(12) as usize;
+                        syscall_gas!(
+                            interpreter,
+                            memory_expansion_cost(&mut rvemu.memory, dest_offset, size as u64)
+                        );
                         let data = &interpreter.return_data_buffer.as_ref()[offset..offset + size];
                         debug!(
                             "> RETURNDATACOPY [memory_offset: {}, offset: {}, size: {}]\n{}",
@@ -388,22 +525,43 @@ fn execute_riscv(
                             .get_dram_slice(dest_offset..(dest_offset + size as u64))?;
                         return_memory.copy_from_slice(data);
                     }
-                    Syscall::Call => return execute_call(emu, interpreter, host, false),
-                    Syscall::StaticCall => return execute_call(emu, interpreter, host, true),
-                    Syscall::Create => return execute_create(emu, interpreter, host),
+                    Syscall::Call => {
+                        rvemu.pending_call = true;
+                        return execute_call(emu, interpreter, host, CallScheme::Call);
+                    }
+                    Syscall::StaticCall => {
+                        rvemu.pending_call = true;
+                        return execute_call(emu, interpreter, host, CallScheme::StaticCall);
+                    }
+                    Syscall::DelegateCall => {
+                        rvemu.pending_call = true;
+                        return execute_call(emu, interpreter, host, CallScheme::DelegateCall);
+                    }
+                    Syscall::Create => return execute_create(emu, interpreter, host, false),
+                    Syscall::Create2 => return execute_create(emu, interpreter, host, true),
                     Syscall::ReturnCreateAddress => {
-                        debug!("> RETURNCREATEDADDRESS: {:?}", &rvemu.created_address);
-                        let dest_offset = emu.cpu.xregs.read(10);
-                        let addr = rvemu
-                            .created_address
-                            .expect("Unable to get created address");
-
-                        // write return data to memory
-                        let return_memory = emu
-                            .cpu
-                            .bus
-                            .get_dram_slice(dest_offset..(dest_offset + 20_u64))?;
-                        return_memory.copy_from_slice(addr.as_slice());
+                        // The create outcome pushes the created address (or zero if it failed)
+                        // onto the stack, and leaves the revert data in the return data buffer
+                        let created = interpreter.stack.pop().unwrap_or_default();
+                        debug!("> RETURNCREATEDADDRESS: {:?}", created);
+                        let status = if created.is_zero() {
+                            0
+                        } else {
+                            // write return data to memory
+                            let dest_offset = emu.cpu.xregs.read(10);
+                            let addr = Address::from_word(created.into());
+                            syscall_gas!(
+                                interpreter,
+                                memory_expansion_cost(&mut rvemu.memory, dest_offset, 20)
+                            );
+                            let return_memory = emu
+                                .cpu
+                                .bus
+                                .get_dram_slice(dest_offset..(dest_offset + 20_u64))?;
+                            return_memory.copy_from_slice(addr.as_slice());
+                            1
+                        };
+                        emu.cpu.xregs.write(10, status);
                     }
                     Syscall::Revert => {
                         let ret_offset: u64 = emu.cpu.xregs.read(10);
@@ -415,7 +573,45 @@ fn execute_riscv(
                             result: InterpreterResult {
                                 result: InstructionResult::Revert,
                                 output: Bytes::from(data_bytes),
-                                gas: interpreter.gas, // FIXME: gas is not correct
+                                gas: interpreter.gas,
+                            },
+                        });
+                    }
+                    Syscall::SelfDestruct => {
+                        // accounts can't be destroyed within a static call
+                        if interpreter.is_static {
+                            return return_halt(
+                                interpreter,
+                                InstructionResult::StateChangeDuringStaticCall,
+                            );
+                        }
+
+                        let beneficiary = read_address(emu);
+                        let address = interpreter.contract.target_address;
+                        // since Cancun, the account is only deleted if created in the same tx
+                        let Some(res) = host.selfdestruct(address, beneficiary) else {
+                            return return_revert(interpreter, interpreter.gas.spent());
+                        };
+                        debug!(
+                            "> SELFDESTRUCT ({}) - Beneficiary: {}",
+                            address, beneficiary
+                        );
+
+                        // Sending the balance to an empty account creates it (EIP-161)
+                        let mut gas_cost = gas::SELFDESTRUCT;
+                        if res.data.had_value && !res.data.target_exists {
+                            gas_cost += gas::SELFDESTRUCT_NEW_ACCOUNT;
+                        }
+                        if res.is_cold {
+                            gas_cost += gas::ACCOUNT_ACCESS_COLD;
+                        }
+                        syscall_gas!(interpreter, gas_cost);
+
+                        return Ok(InterpreterAction::Return {
+                            result: InterpreterResult {
+                                result: InstructionResult::SelfDestruct,
+                                output: Bytes::new(),
+                                gas: interpreter.gas,
                             },
                         });
                     }
@@ -518,12 +714,132 @@ fn execute_riscv(
                         let third_u64 = u64::from_be_bytes(padded_bytes);
                         emu.cpu.xregs.write(12, third_u64);
                     }
+                    Syscall::Address => {
+                        write_address(emu, interpreter.contract.target_address);
+                    }
+                    Syscall::Coinbase => {
+                        write_address(emu, host.env().block.coinbase);
+                    }
+                    Syscall::PrevRandao => {
+                        let prevrandao = host.env().block.prevrandao.unwrap_or_default();
+                        write_u256(emu, prevrandao.into());
+                    }
+                    Syscall::BlobBaseFee => {
+                        let blob_base_fee =
+                            host.env().block.get_blob_gasprice().unwrap_or_default();
+                        write_u256(emu, U256::from(blob_base_fee));
+                    }
+                    Syscall::Gas => {
+                        emu.cpu.xregs.write(10, interpreter.gas.remaining());
+                    }
+                    Syscall::Balance => {
+                        let addr = read_address(emu);
+                        let Some(balance) = host.balance(addr) else {
+                            return return_revert(interpreter, interpreter.gas.spent());
+                        };
+                        debug!("> BALANCE ({}): {}", addr, balance.data);
+                        syscall_gas!(interpreter, account_access_cost(balance.is_cold));
+                        write_u256(emu, balance.data);
+                    }
+                    Syscall::SelfBalance => {
+                        let addr = interpreter.contract.target_address;
+                        let Some(balance) = host.balance(addr) else {
+                            return return_revert(interpreter, interpreter.gas.spent());
+                        };
+                        debug!("> SELFBALANCE ({}): {}", addr, balance.data);
+                        syscall_gas!(interpreter, gas::SELFBALANCE);
+                        write_u256(emu, balance.data);
+                    }
+                    Syscall::CodeSize => {
+                        let size = interpreter.contract.bytecode.original_byte_len();
+                        debug!("> CODESIZE: {}", size);
+                        emu.cpu.xregs.write(10, size as u64);
+                    }
+                    Syscall::ExtCodeSize => {
+                        let addr = read_address(emu);
+                        let Some(code) = host.code(addr) else {
+                            return return_revert(interpreter, interpreter.gas.spent());
+                        };
+                        debug!("> EXTCODESIZE ({}): {}", addr, code.state_load.data.len());
+                        syscall_gas!(interpreter, account_access_cost(code.state_load.is_cold));
+                        emu.cpu.xregs.write(10, code.state_load.data.len() as u64);
+                    }
+                    Syscall::ExtCodeHash => {
+                        let addr = read_address(emu);
+                        let Some(code_hash) = host.code_hash(addr) else {
+                            return return_revert(interpreter, interpreter.gas.spent());
+                        };
+                        debug!("> EXTCODEHASH ({}): {}", addr, code_hash.state_load.data);
+                        syscall_gas!(
+                            interpreter,
+                            account_access_cost(code_hash.state_load.is_cold)
+                        );
+                        write_u256(emu, code_hash.state_load.data.into());
+                    }
+                    Syscall::ExtCodeCopy => {
+                        let addr = read_address(emu);
+                        let dest_offset: u64 = emu.cpu.xregs.read(13);
+                        let code_offset: u64 = emu.cpu.xregs.read(14);
+                        let size: u64 = emu.cpu.xregs.read(15);
+                        let Some(code) = host.code(addr) else {
+                            return return_revert(interpreter, interpreter.gas.spent());
+                        };
+                        debug!(
+                            "> EXTCODECOPY ({}) [memory_offset: {}, offset: {}, size: {}]",
+                            addr, dest_offset, code_offset, size
+                        );
+                        syscall_gas!(
+                            interpreter,
+                            account_access_cost(code.state_load.is_cold)
+                                + gas::COPY_WORD * size.div_ceil(32)
+                                + memory_expansion_cost(&mut rvemu.memory, dest_offset, size)
+                        );
+
+                        // copy the code, padding with zeros past its end
+                        let code = code.state_load.data;
+                        let memory = dram_slice(emu, dest_offset, size)?;
+                        memory.fill(0);
+                        if let Some(code) = code.get(code_offset as usize..) {
+                            let len = code.len().min(memory.len());
+                            memory[..len].copy_from_slice(&code[..len]);
+                        }
+                    }
+                    Syscall::BlockHash => {
+                        syscall_gas!(interpreter, gas::BLOCKHASH);
+                        let number: u64 = emu.cpu.xregs.read(10);
+                        let current = host.env().block.number.saturating_to::<u64>();
+
+                        // only the hashes of the most recent blocks are available
+                        let hash = if number < current && current - number <= BLOCK_HASH_HISTORY {
+                            let Some(hash) = host.block_hash(number) else {
+                                return return_revert(interpreter, interpreter.gas.spent());
+                            };
+                            hash
+                        } else {
+                            B256::ZERO
+                        };
+                        debug!("> BLOCKHASH ({}): {}", number, hash);
+                        write_u256(emu, hash.into());
+                    }
                     Syscall::Log => {
+                        // logs can't be emitted within a static call
+                        if interpreter.is_static {
+                            return return_halt(
+                                interpreter,
+                                InstructionResult::StateChangeDuringStaticCall,
+                            );
+                        }
+
                         let data_ptr: u64 = emu.cpu.xregs.read(10);
                         let data_size: u64 = emu.cpu.xregs.read(11);
                         let topics_ptr: u64 = emu.cpu.xregs.read(12);
                         let topics_size: u64 = emu.cpu.xregs.read(13);
 
+                        // LOG0-LOG4: logs can't have more than 4 topics
+                        if topics_size > 4 {
+                            return return_revert(interpreter, interpreter.gas.spent());
+                        }
+
                         // Read data
                         let data = if data_size == 0 {
                             Vec::new()
@@ -559,13 +875,19 @@ fn execute_riscv(
                     }
                 }
             }
-            Ok(_) => {
-                trace!("Successful instruction at PC: {:#x}", emu.cpu.pc);
-                continue;
+            Ok(inst) => {
+                instruction_gas += schedule.instruction_cost(inst as u32);
+
+                // Abort as soon as the frame can't afford the executed instructions,
+                // rather than waiting for the next syscall (which might never happen)
+                if instruction_gas > interpreter.gas.remaining() {
+                    debug!("Out of gas at PC: {:#x}", emu.cpu.pc);
+                    syscall_gas!(interpreter, instruction_gas);
+                }
             }
             Err(e) => {
                 debug!("Execution error: {:#?}", e);
-                syscall_gas!(interpreter, r55_gas_used(&emu.cpu.inst_counter));
+                syscall_gas!(interpreter, instruction_gas);
                 return return_revert(interpreter, interpreter.gas.spent());
             }
         }
@@ -576,17 +898,26 @@ fn execute_call(
     emu: &mut Emulator,
     interpreter: &mut Interpreter,
     host: &mut dyn Host,
-    is_static: bool,
+    scheme: CallScheme,
 ) -> Result<InterpreterAction> {
     let a0: u64 = emu.cpu.xregs.read(10);
     let a1: u64 = emu.cpu.xregs.read(11);
     let a2: u64 = emu.cpu.xregs.read(12);
     let addr = Address::from_word(U256::from_limbs([a0, a1, a2, 0]).into());
-    let value: u64 = emu.cpu.xregs.read(13);
+
+    // Delegate calls don't transfer value, so the following args start one register earlier
+    let (value, args_reg) = match scheme {
+        CallScheme::DelegateCall => (U256::ZERO, 13),
+        _ => {
+            let value_offset: u64 = emu.cpu.xregs.read(13);
+            (read_value(emu, value_offset)?, 14)
+        }
+    };
 
     // Get calldata
-    let args_offset: u64 = emu.cpu.xregs.read(14);
-    let args_size: u64 = emu.cpu.xregs.read(15);
+    let args_offset: u64 = emu.cpu.xregs.read(args_reg);
+    let args_size: u64 = emu.cpu.xregs.read(args_reg + 1);
+    let requested_gas: u64 = emu.cpu.xregs.read(args_reg + 2);
     let calldata: Bytes = emu
         .cpu
         .bus
@@ -597,7 +928,7 @@ fn execute_call(
 
     // Calculate gas cost of the call
     // TODO: check correctness (tried using evm.codes as ref but i'm no gas wizard)
-    // TODO: unsure whether memory expansion cost is missing (should be captured in the risc-v costs)
+    // Note: memory expansion is charged as the calldata is written by the RISC-V instructions
     let (empty_account_cost, addr_access_cost) = match host.load_account_delegated(addr) {
         Some(account) => {
             if account.is_cold {
@@ -608,28 +939,50 @@ fn execute_call(
         }
         None => (gas::CALL_EMPTY_ACCOUNT, gas::CALL_NEW_ACCOUNT),
     };
-    let value_cost = if value != 0 { gas::CALL_VALUE } else { 0 };
+    let value_cost = if value.is_zero() { 0 } else { gas::CALL_VALUE };
     let call_gas_cost = empty_account_cost + addr_access_cost + value_cost;
     syscall_gas!(interpreter, call_gas_cost);
 
     // proactively spend gas limit as the remaining will be refunded (otherwise it underflows)
-    let call_gas_limit = interpreter.gas.remaining();
+    let mut call_gas_limit = gas::forwarded_gas(requested_gas, interpreter.gas.remaining());
     syscall_gas!(interpreter, call_gas_limit);
 
-    debug!("> {}Call context:", if is_static { "Static" } else { "" });
-    debug!("  - Caller: {}", interpreter.contract.target_address);
-    debug!("  - Target Address: {}", addr);
-    debug!("  - Value: {}", value);
+    // value transfers grant the callee a stipend on top of the forwarded gas
+    if !value.is_zero() {
+        call_gas_limit += gas::CALL_STIPEND;
+    }
+
+    // Delegate calls run the target's code within the current context (storage, caller and value)
+    let (target_address, caller, value, is_static) = match scheme {
+        CallScheme::DelegateCall => (
+            interpreter.contract.target_address,
+            interpreter.contract.caller,
+            CallValue::Apparent(interpreter.contract.call_value),
+            interpreter.is_static,
+        ),
+        _ => (
+            addr,
+            interpreter.contract.target_address,
+            CallValue::Transfer(value),
+            scheme == CallScheme::StaticCall,
+        ),
+    };
+
+    debug!("> {:?} context:", scheme);
+    debug!("  - Caller: {}", caller);
+    debug!("  - Target Address: {}", target_address);
+    debug!("  - Bytecode Address: {}", addr);
+    debug!("  - Value: {}", value.get());
     debug!("  - Calldata: {:?}", calldata);
     Ok(InterpreterAction::Call {
         inputs: Box::new(CallInputs {
             input: calldata,
             gas_limit: call_gas_limit,
-            target_address: addr,
+            target_address,
             bytecode_address: addr,
-            caller: interpreter.contract.target_address,
-            value: CallValue::Transfer(U256::from(value)),
-            scheme: CallScheme::Call,
+            caller,
+            value,
+            scheme,
             is_static,
             is_eof: false,
             return_memory_offset: 0..0, // handled with RETURNDATACOPY
@@ -641,8 +994,10 @@ fn execute_create(
     emu: &mut Emulator,
     interpreter: &mut Interpreter,
     _host: &mut dyn Host,
+    is_create2: bool,
 ) -> Result<InterpreterAction> {
-    let value: u64 = emu.cpu.xregs.read(10);
+    let value_offset: u64 = emu.cpu.xregs.read(10);
+    let value = read_value(emu, value_offset)?;
 
     // Get initcode
     let args_offset: u64 = emu.cpu.xregs.read(11);
@@ -655,15 +1010,28 @@ fn execute_create(
         .to_vec()
         .into();
 
+    // CREATE2 hashes the initcode to derive the deployment address
+    let (scheme, requested_gas) = if is_create2 {
+        let salt_offset: u64 = emu.cpu.xregs.read(13);
+        let salt = U256::from_be_slice(dram_slice(emu, salt_offset, 32)?);
+        (CreateScheme::Create2 { salt }, emu.cpu.xregs.read(14))
+    } else {
+        (CreateScheme::Create, emu.cpu.xregs.read(13))
+    };
+
     // TODO: calculate gas cost properly
-    let create_gas_cost = gas::CREATE_BASE;
+    let hash_gas_cost = match scheme {
+        CreateScheme::Create2 { .. } => gas::CREATE2_WORD * (init_code.len() as u64).div_ceil(32),
+        _ => 0,
+    };
+    let create_gas_cost = gas::CREATE_BASE + hash_gas_cost;
     syscall_gas!(interpreter, create_gas_cost);
 
     // proactively spend gas limit as the remaining will be refunded (otherwise it underflows)
-    let create_gas_limit = interpreter.gas.remaining();
+    let create_gas_limit = gas::forwarded_gas(requested_gas, interpreter.gas.remaining());
     syscall_gas!(interpreter, create_gas_limit);
 
-    debug!("> CREATE CTX:");
+    debug!("> {:?} CTX:", scheme);
     debug!("  - Caller: {}", interpreter.contract.target_address);
     debug!("  - Value: {}", value);
     debug!("  - Initcode size: {:?}", init_code.len());
@@ -672,12 +1040,83 @@ fn execute_create(
             init_code,
             gas_limit: create_gas_limit,
             caller: interpreter.contract.target_address,
-            value: U256::from(value),
-            scheme: CreateScheme::Create,
+            value,
+            scheme,
         }),
     })
 }
 
+/// Executes a single RISC-V instruction, returning its raw encoding.
+///
+/// On `ecall` the program counter is moved past the instruction, so that execution resumes
+/// at the next one once the syscall has been handled.
+fn step(emu: &mut Emulator) -> core::result::Result<u64, Exception> {
+    let result = emu.cpu.execute();
+    if let Err(Exception::EnvironmentCallFromMMode) = result {
+        emu.cpu.pc += 4;
+    }
+    result
+}
+
+/// Reads the 256-bit big-endian value stored at a given offset (a null offset means no value)
+fn read_value(emu: &mut Emulator, value_offset: u64) -> Result<U256> {
+    if value_offset == 0 {
+        return Ok(U256::ZERO);
+    }
+
+    Ok(U256::from_be_slice(dram_slice(emu, value_offset, 32)?))
+}
+
+/// Reads an address passed as 3 u64s in `a0`-`a2`
+fn read_address(emu: &Emulator) -> Address {
+    let a0: u64 = emu.cpu.xregs.read(10);
+    let a1: u64 = emu.cpu.xregs.read(11);
+    let a2: u64 = emu.cpu.xregs.read(12);
+    Address::from_word(U256::from_limbs([a0, a1, a2, 0]).into())
+}
+
+/// Writes an address into `a0`-`a2`, as 3 big-endian u64s
+fn write_address(emu: &mut Emulator, addr: Address) {
+    let bytes = addr.as_slice();
+    let mut padded_bytes = [0u8; 8];
+    padded_bytes[..4].copy_from_slice(&bytes[16..20]);
+
+    emu.cpu
+        .xregs
+        .write(10, u64::from_be_bytes(bytes[0..8].try_into().unwrap()));
+    emu.cpu
+        .xregs
+        .write(11, u64::from_be_bytes(bytes[8..16].try_into().unwrap()));
+    emu.cpu.xregs.write(12, u64::from_be_bytes(padded_bytes));
+}
+
+/// Writes a 256-bit value into `a0`-`a3`, as little-endian limbs
+fn write_u256(emu: &mut Emulator, value: U256) {
+    let limbs = value.as_limbs();
+    emu.cpu.xregs.write(10, limbs[0]);
+    emu.cpu.xregs.write(11, limbs[1]);
+    emu.cpu.xregs.write(12, limbs[2]);
+    emu.cpu.xregs.write(13, limbs[3]);
+}
+
+fn account_access_cost(is_cold: bool) -> u64 {
+    if is_cold {
+        gas::ACCOUNT_ACCESS_COLD
+    } else {
+        gas::ACCOUNT_ACCESS_WARM
+    }
+}
+
+/// Records an access of `size` bytes at `address`, by the program or by the host on its
+/// behalf, and returns the gas of the memory expansion it causes
+fn memory_expansion_cost(memory: &mut MemoryTracker, address: u64, size: u64) -> u64 {
+    let touched_pages = memory.touched_pages();
+    if memory.touch(address, size) == 0 {
+        return 0;
+    }
+    gas::memory_cost(memory.touched_pages()) - gas::memory_cost(touched_pages)
+}
+
 /// Returns RISC-V DRAM slice in a given size range, starts with a given offset
 fn dram_slice(emu: &mut Emulator, ret_offset: u64, ret_size: u64) -> Result<&mut [u8]> {
     if ret_size != 0 {
@@ -689,34 +1128,3 @@ fn dram_slice(emu: &mut Emulator, ret_offset: u64, ret_size: u64) -> Result<&mut
         Ok(&mut [])
     }
 }
-
-fn r55_gas_used(inst_count: &BTreeMap<String, u64>) -> u64 {
-    let total_cost = inst_count
-        .iter()
-        .map(|(inst_name, count)|
-            // Gas cost = number of instructions * cycles per instruction
-            match inst_name.as_str() {
-                // Gas map to approximate cost of each instruction
-                // References:
-                // http://ithare.com/infographics-operation-costs-in-cpu-clock-cycles/
-                // https://www.evm.codes/?fork=cancun#54
-                // Division and remainder
-                s if s.starts_with("div") || s.starts_with("rem") => count * 25,
-                // Multiplications
-                s if s.starts_with("mul") => count * 5,
-                // Loads
-                "lb" | "lh" | "lw" | "ld" | "lbu" | "lhu" | "lwu" => count * 3, // Cost analagous to `MLOAD`
-                // Stores
-                "sb" | "sh" | "sw" | "sd" | "sc.w" | "sc.d" => count * 3, // Cost analagous to `MSTORE`
-                // Branching
-                "beq" | "bne" | "blt" | "bge" | "bltu" | "bgeu" | "jal" | "jalr" => count * 3,
-                _ => *count, // All other instructions including `add` and `sub`
-        })
-        .sum::<u64>();
-
-    // This is the minimum 'gas used' to ABI decode 'empty' calldata into Rust type arguments. Real calldata will take more gas.
-    // Internalising this would focus gas metering more on the function logic
-    let abi_decode_cost = 9_175_538;
-
-    total_cost - abi_decode_cost
-}
@@ -1,17 +1,54 @@
+use eth_riscv_interpreter::PAGE_SIZE;
+
+mod schedule;
+pub use schedule::{GasSchedule, InstructionClass};
+
 // Standard EVM operation costs
 pub const SLOAD_COLD: u64 = 2100;
 pub const SLOAD_WARM: u64 = 100;
 pub const SSTORE_COLD: u64 = 2200;
 pub const SSTORE_WARM: u64 = 100;
+pub const TLOAD: u64 = 100;
+pub const TSTORE: u64 = 100;
 
 // Call-related costs
 pub const CALL_EMPTY_ACCOUNT: u64 = 25000;
 pub const CALL_NEW_ACCOUNT: u64 = 2600;
 pub const CALL_VALUE: u64 = 9000;
 pub const CALL_BASE: u64 = 100;
+pub const CALL_STIPEND: u64 = 2300;
+
+// Environment-related costs
+pub const ACCOUNT_ACCESS_COLD: u64 = 2600;
+pub const ACCOUNT_ACCESS_WARM: u64 = 100;
+pub const BLOCKHASH: u64 = 20;
+pub const SELFBALANCE: u64 = 5;
+pub const COPY_WORD: u64 = 3;
+
+// Selfdestruct-related costs
+pub const SELFDESTRUCT: u64 = 5000;
+pub const SELFDESTRUCT_NEW_ACCOUNT: u64 = 25000;
 
 // Create-related costs
 pub const CREATE_BASE: u64 = 32000;
+pub const CREATE2_WORD: u64 = 6;
+
+/// Returns the gas forwarded to a sub-call or create, capping the requested limit to all but
+/// one 64th of the remaining gas (EIP-150)
+pub fn forwarded_gas(requested: u64, remaining: u64) -> u64 {
+    requested.min(remaining - remaining / 64)
+}
+
+// Memory-related costs
+pub const MEMORY_WORD: u64 = 3;
+pub const MEMORY_QUADRATIC_DIVISOR: u64 = 512;
+
+/// Returns the cost of having `pages` RISC-V memory pages touched, priced like EVM memory:
+/// `MEMORY_WORD * words + words² / MEMORY_QUADRATIC_DIVISOR`
+pub fn memory_cost(pages: u64) -> u64 {
+    let words = pages * (PAGE_SIZE / 32);
+    MEMORY_WORD * words + words * words / MEMORY_QUADRATIC_DIVISOR
+}
 
 // Macro to handle gas accounting for syscalls.
 // Returns OutOfGas InterpreterResult if gas limit is exceeded.
@@ -0,0 +1,257 @@
+//! RISC-V instruction gas schedule.
+//!
+//! Every instruction executed by the emulator is classified by its encoding and priced
+//! according to a versioned cost table, so that gas numbers are deterministic and comparable
+//! across contracts (and across R55 releases, as long as the schedule version matches).
+
+/// Class of a RV64IMAC instruction, as far as gas pricing is concerned.
+///
+/// Compressed instructions are classified as their 32-bit equivalent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(usize)]
+pub enum InstructionClass {
+    /// Integer register-register and register-immediate operations (incl. `lui` and `auipc`)
+    Alu,
+    /// Multiplications (`mul`, `mulh`, `mulhsu`, `mulhu`, `mulw`)
+    Mul,
+    /// Divisions and remainders (`div`, `divu`, `rem`, `remu` and their word variants)
+    Div,
+    /// Memory loads
+    Load,
+    /// Memory stores
+    Store,
+    /// Conditional branches
+    Branch,
+    /// Unconditional jumps (`jal`, `jalr`)
+    Jump,
+    /// Load-reserved (`lr.w`, `lr.d`)
+    LoadReserved,
+    /// Store-conditional (`sc.w`, `sc.d`)
+    StoreConditional,
+    /// Atomic read-modify-write memory operations (`amo*`)
+    AtomicMemoryOp,
+    /// Memory ordering (`fence`, `fence.i`)
+    Fence,
+    /// Control and status register access (`csrr*`)
+    Csr,
+    /// Environment calls and privileged instructions (`ecall`, `ebreak`, `mret`, `wfi`)
+    System,
+    /// Unrecognized encoding
+    Unknown,
+}
+
+impl InstructionClass {
+    /// Number of instruction classes, used to size the gas schedule table.
+    pub const COUNT: usize = InstructionClass::Unknown as usize + 1;
+
+    /// Classifies a raw instruction, either a 32-bit or a (16-bit) compressed one.
+    pub fn decode(inst: u32) -> Self {
+        if inst & 0b11 == 0b11 {
+            Self::decode_standard(inst)
+        } else {
+            Self::decode_compressed(inst as u16)
+        }
+    }
+
+    fn decode_standard(inst: u32) -> Self {
+        let opcode = inst & 0x7f;
+        let funct3 = (inst >> 12) & 0x7;
+        let funct7 = inst >> 25;
+
+        match opcode {
+            // LOAD
+            0x03 => Self::Load,
+            // MISC-MEM
+            0x0f => Self::Fence,
+            // OP-IMM, AUIPC, OP-IMM-32, LUI
+            0x13 | 0x17 | 0x1b | 0x37 => Self::Alu,
+            // STORE
+            0x23 => Self::Store,
+            // AMO
+            0x2f => match inst >> 27 {
+                0b00010 => Self::LoadReserved,
+                0b00011 => Self::StoreConditional,
+                _ => Self::AtomicMemoryOp,
+            },
+            // OP, OP-32 (the M extension is encoded with `funct7 == 1`)
+            0x33 | 0x3b if funct7 == 0x01 => {
+                if funct3 < 4 {
+                    Self::Mul
+                } else {
+                    Self::Div
+                }
+            }
+            0x33 | 0x3b => Self::Alu,
+            // BRANCH
+            0x63 => Self::Branch,
+            // JALR, JAL
+            0x67 | 0x6f => Self::Jump,
+            // SYSTEM
+            0x73 if funct3 == 0 => Self::System,
+            0x73 => Self::Csr,
+            _ => Self::Unknown,
+        }
+    }
+
+    fn decode_compressed(inst: u16) -> Self {
+        let quadrant = inst & 0b11;
+        let funct3 = (inst >> 13) & 0x7;
+
+        match (quadrant, funct3) {
+            // C.ADDI4SPN
+            (0b00, 0b000) => Self::Alu,
+            // C.FLD, C.LW, C.LD
+            (0b00, 0b001..=0b011) => Self::Load,
+            // C.FSD, C.SW, C.SD
+            (0b00, 0b101..=0b111) => Self::Store,
+            // C.ADDI, C.ADDIW, C.LI, C.ADDI16SP, C.LUI, C.SRLI, C.SRAI, C.ANDI, C.SUB, ...
+            (0b01, 0b000..=0b100) => Self::Alu,
+            // C.J
+            (0b01, 0b101) => Self::Jump,
+            // C.BEQZ, C.BNEZ
+            (0b01, 0b110 | 0b111) => Self::Branch,
+            // C.SLLI
+            (0b10, 0b000) => Self::Alu,
+            // C.FLDSP, C.LWSP, C.LDSP
+            (0b10, 0b001..=0b011) => Self::Load,
+            // C.JR, C.MV, C.EBREAK, C.JALR, C.ADD
+            (0b10, 0b100) => {
+                let bit12 = (inst >> 12) & 0x1;
+                let rs1 = (inst >> 7) & 0x1f;
+                let rs2 = (inst >> 2) & 0x1f;
+                match (bit12, rs1, rs2) {
+                    (0, _, 0) => Self::Jump,
+                    (0, _, _) => Self::Alu,
+                    (_, 0, 0) => Self::System,
+                    (_, _, 0) => Self::Jump,
+                    (_, _, _) => Self::Alu,
+                }
+            }
+            // C.FSDSP, C.SWSP, C.SDSP
+            (0b10, 0b101..=0b111) => Self::Store,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Versioned gas cost table, indexed by [`InstructionClass`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasSchedule {
+    version: u16,
+    costs: [u64; InstructionClass::COUNT],
+}
+
+impl GasSchedule {
+    /// Initial schedule.
+    ///
+    /// Costs approximate the CPU cycles of each instruction class, with memory accesses
+    /// priced analogously to `MLOAD`/`MSTORE`.
+    /// References:
+    /// - http://ithare.com/infographics-operation-costs-in-cpu-clock-cycles/
+    /// - https://www.evm.codes/?fork=cancun
+    pub const V1: GasSchedule = GasSchedule {
+        version: 1,
+        costs: [
+            1,  // Alu
+            5,  // Mul
+            25, // Div
+            3,  // Load
+            3,  // Store
+            3,  // Branch
+            3,  // Jump
+            3,  // LoadReserved
+            3,  // StoreConditional
+            6,  // AtomicMemoryOp
+            1,  // Fence
+            1,  // Csr
+            1,  // System
+            1,  // Unknown
+        ],
+    };
+
+    /// Returns the schedule used to meter RISC-V execution.
+    pub const fn latest() -> &'static GasSchedule {
+        &Self::V1
+    }
+
+    pub const fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// Returns the gas cost of an instruction class.
+    pub const fn cost(&self, class: InstructionClass) -> u64 {
+        self.costs[class as usize]
+    }
+
+    /// Returns the gas cost of a raw instruction.
+    pub fn instruction_cost(&self, inst: u32) -> u64 {
+        self.cost(InstructionClass::decode(inst))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_standard() {
+        let test_cases = [
+            (0x00100093, InstructionClass::Alu),          // addi x1, x0, 1
+            (0x000010b7, InstructionClass::Alu),          // lui x1, 1
+            (0x023100b3, InstructionClass::Mul),          // mul x1, x2, x3
+            (0x023150b3, InstructionClass::Div),          // divu x1, x2, x3
+            (0x023160bb, InstructionClass::Div),          // remw x1, x2, x3
+            (0x00013083, InstructionClass::Load),         // ld x1, 0(x2)
+            (0x00113023, InstructionClass::Store),        // sd x1, 0(x2)
+            (0x00208063, InstructionClass::Branch),       // beq x1, x2, 0
+            (0x0000006f, InstructionClass::Jump),         // jal x0, 0
+            (0x00008067, InstructionClass::Jump),         // jalr x0, 0(x1)
+            (0x100130af, InstructionClass::LoadReserved), // lr.d x1, (x2)
+            (0x183130af, InstructionClass::StoreConditional), // sc.d x1, x3, (x2)
+            (0x003130af, InstructionClass::AtomicMemoryOp), // amoadd.d x1, x3, (x2)
+            (0x0ff0000f, InstructionClass::Fence),        // fence
+            (0x300020f3, InstructionClass::Csr),          // csrrs x1, mstatus, x0
+            (0x00000073, InstructionClass::System),       // ecall
+        ];
+
+        for (inst, expected) in test_cases {
+            assert_eq!(InstructionClass::decode(inst), expected, "{:#010x}", inst);
+        }
+    }
+
+    #[test]
+    fn test_decode_compressed() {
+        let test_cases = [
+            (0x0001, InstructionClass::Alu),    // c.nop
+            (0x4188, InstructionClass::Load),   // c.lw a0, 0(a1)
+            (0xe406, InstructionClass::Store),  // c.sdsp ra, 8(sp)
+            (0xa001, InstructionClass::Jump),   // c.j 0
+            (0xc101, InstructionClass::Branch), // c.beqz a0, 0
+            (0x8082, InstructionClass::Jump),   // c.jr ra
+            (0x852e, InstructionClass::Alu),    // c.mv a0, a1
+            (0x952e, InstructionClass::Alu),    // c.add a0, a1
+            (0x9502, InstructionClass::Jump),   // c.jalr a0
+            (0x9002, InstructionClass::System), // c.ebreak
+        ];
+
+        for (inst, expected) in test_cases {
+            assert_eq!(InstructionClass::decode(inst), expected, "{:#06x}", inst);
+        }
+    }
+
+    #[test]
+    fn test_schedule_v1_costs() {
+        let schedule = GasSchedule::latest();
+        assert_eq!(schedule.version(), 1);
+
+        // Compressed instructions are priced as their 32-bit equivalent
+        assert_eq!(
+            schedule.instruction_cost(0x4188),
+            schedule.instruction_cost(0x00013083)
+        );
+        assert_eq!(schedule.instruction_cost(0x00100093), 1);
+        assert_eq!(schedule.instruction_cost(0x023100b3), 5);
+        assert_eq!(schedule.instruction_cost(0x023150b3), 25);
+        assert_eq!(schedule.instruction_cost(0x003130af), 6);
+    }
+}
@@ -9,6 +9,16 @@ pub const EVM_CALLER_BYTECODE: &[u8] =
     include_bytes!("../../../r55-output-bytecode/evm-caller.bin");
 pub const ERC20_BYTECODE: &[u8] = include_bytes!("../../../r55-output-bytecode/erc20.bin");
 pub const ERC20X_BYTECODE: &[u8] = include_bytes!("../../../r55-output-bytecode/erc20x.bin");
+pub const ENVIRONMENT_BYTECODE: &[u8] =
+    include_bytes!("../../../r55-output-bytecode/environment.bin");
+pub const TRANSIENT_BYTECODE: &[u8] = include_bytes!("../../../r55-output-bytecode/transient.bin");
+pub const WALLET_BYTECODE: &[u8] = include_bytes!("../../../r55-output-bytecode/wallet.bin");
+pub const VAULT_BYTECODE: &[u8] = include_bytes!("../../../r55-output-bytecode/vault.bin");
+pub const ALLOWLIST_BYTECODE: &[u8] = include_bytes!("../../../r55-output-bytecode/allowlist.bin");
+pub const EPHEMERAL_BYTECODE: &[u8] = include_bytes!("../../../r55-output-bytecode/ephemeral.bin");
+pub const COUNTER_BYTECODE: &[u8] = include_bytes!("../../../r55-output-bytecode/counter.bin");
+pub const COUNTER_CACHED_BYTECODE: &[u8] =
+    include_bytes!("../../../r55-output-bytecode/counter-cached.bin");
 
 pub fn get_bytecode(contract_name: &str) -> Bytes {
     let initcode = match contract_name {
@@ -16,6 +26,14 @@ pub fn get_bytecode(contract_name: &str) -> Bytes {
         "evm_caller" => EVM_CALLER_BYTECODE,
         "erc20" => ERC20_BYTECODE,
         "erc20x" => ERC20X_BYTECODE,
+        "environment" => ENVIRONMENT_BYTECODE,
+        "transient" => TRANSIENT_BYTECODE,
+        "wallet" => WALLET_BYTECODE,
+        "vault" => VAULT_BYTECODE,
+        "allowlist" => ALLOWLIST_BYTECODE,
+        "ephemeral" => EPHEMERAL_BYTECODE,
+        "counter" => COUNTER_BYTECODE,
+        "counter_cached" => COUNTER_CACHED_BYTECODE,
         _ => return Bytes::new(),
     };
 
@@ -1,6 +1,6 @@
 mod error;
 pub mod exec;
-mod gas;
+pub mod gas;
 
 mod generated;
 pub use generated::get_bytecode;
@@ -10,13 +10,13 @@ pub mod test_utils;
 #[cfg(test)]
 mod tests {
     use crate::{
-        exec::{deploy_contract, run_tx},
+        exec::{create2_address, deploy_contract, run_tx, run_tx_with_gas_limit},
         get_bytecode,
         test_utils::*,
     };
 
     use alloy_core::hex::{self, ToHexExt};
-    use alloy_primitives::B256;
+    use alloy_primitives::{B256, U64};
     use alloy_sol_types::SolValue;
 
     fn setup_erc20(owner: Address) -> (InMemoryDB, Address) {
@@ -298,7 +298,7 @@ mod tests {
         let only_owner_result =
             run_tx(&mut db, &erc20, calldata_mint, &BOB).expect_err("Mint transaction succeeded");
         assert!(
-            only_owner_result.matches_custom_error("ERC20Error::OnlyOwner"),
+            only_owner_result.matches_custom_error("OnlyOwner"),
             "Incorrect error"
         );
 
@@ -312,7 +312,7 @@ mod tests {
                 .expect_err("Transfer transaction succeeded");
         assert!(
             insufficient_balance_result.matches_custom_error_with_args(
-                "ERC20Error::InsufficientBalance(uint256)",
+                "InsufficientBalance(uint256)",
                 value_mint.abi_encode()
             ),
             "Incorrect error signature"
@@ -337,7 +337,7 @@ mod tests {
                 .expect_err("Transfer From tx succeeded");
         assert!(
             insufficient_allowance_result.matches_custom_error_with_args(
-                "ERC20Error::InsufficientAllowance(uint256)",
+                "InsufficientAllowance(uint256)",
                 value_approve.abi_encode()
             ),
             "Incorrect error signature"
@@ -372,7 +372,7 @@ mod tests {
         let only_owner_result = run_tx(&mut db, &erc20x, calldata_x_mint, &BOB)
             .expect_err("Mint transaction succeeded");
         assert!(
-            only_owner_result.matches_custom_error("ERC20Error::OnlyOwner"),
+            only_owner_result.matches_custom_error("OnlyOwner"),
             "Incorrect error"
         );
 
@@ -385,7 +385,7 @@ mod tests {
         let zero_amount_result = run_tx(&mut db, &erc20x, calldata_x_transfer_from.clone(), &BOB)
             .expect_err("Transfer transaction succeeded");
         assert!(
-            zero_amount_result.matches_custom_error("ERC20Error::ZeroAmount"),
+            zero_amount_result.matches_custom_error("ZeroAmount"),
             "Incorrect error signature"
         );
 
@@ -418,6 +418,412 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_create2() {
+        let (mut db, _) = setup_erc20(ALICE);
+        let erc20x = setup_erc20x(&mut db);
+
+        // Precompute the deployment address
+        let salt = B256::with_last_byte(42);
+        let expected = create2_address(
+            erc20x,
+            salt,
+            get_bytecode("erc20"),
+            Some(ALICE.abi_encode()),
+        );
+
+        // Deploy with the same salt and constructor args
+        let selector_x_deploy = get_selector_from_sig("x_deploy_with_salt(address,bytes32)");
+        let calldata_x_deploy = get_calldata(selector_x_deploy, (ALICE, salt).abi_encode());
+        let deploy_result = run_tx(&mut db, &erc20x, calldata_x_deploy, &ALICE).unwrap();
+        assert_eq!(
+            Address::from_slice(&deploy_result.output[12..32]),
+            expected,
+            "Incorrect address"
+        );
+    }
+
+    #[test]
+    fn test_create_failure() {
+        let (mut db, _) = setup_erc20(ALICE);
+        let erc20x = setup_erc20x(&mut db);
+
+        // Deploying with the zero address as owner makes the constructor revert
+        let selector_x_deploy = get_selector_from_sig("x_deploy(address)");
+        let calldata_x_deploy = get_calldata(selector_x_deploy, Address::ZERO.abi_encode());
+        let deploy_result =
+            run_tx(&mut db, &erc20x, calldata_x_deploy, &ALICE).expect_err("Tx succeeded");
+        assert!(
+            deploy_result.matches_custom_error("ZeroAddress"),
+            "Incorrect error"
+        );
+    }
+
+    #[test]
+    fn test_undecodable_call_data() {
+        let (mut db, _) = setup_erc20(ALICE);
+        let erc20x = setup_erc20x(&mut db);
+        let selector_x_mint = get_selector_from_sig("x_mint(address,uint256,address)");
+
+        // Revert data that isn't an `ERC20Error` is bubbled up as is
+        let calldata_x_mint = get_calldata(
+            selector_x_mint,
+            (BOB, U256::from(1e18), erc20x).abi_encode(),
+        );
+        let revert_result =
+            run_tx(&mut db, &erc20x, calldata_x_mint, &ALICE).expect_err("Tx succeeded");
+        assert!(
+            revert_result.matches_string_error("unknown method"),
+            "Incorrect error"
+        );
+
+        // So is an output that isn't a `bool`, like the empty one of an account without code
+        let calldata_x_mint =
+            get_calldata(selector_x_mint, (BOB, U256::from(1e18), CAROL).abi_encode());
+        let output_result =
+            run_tx(&mut db, &erc20x, calldata_x_mint, &ALICE).expect_err("Tx succeeded");
+        assert!(output_result.matches_string_error(""), "Incorrect error");
+    }
+
+    #[test]
+    fn test_delegatecall() {
+        let (mut db, erc20) = setup_erc20(ALICE);
+        let erc20x = setup_erc20x(&mut db);
+
+        // Define fn selectors
+        let selector_mint = get_selector_from_sig("mint(address,uint256)");
+        let selector_x_balance = get_selector_from_sig("x_balance_of(address,address)");
+        let selector_x_delegate_balance =
+            get_selector_from_sig("x_delegate_balance_of(address,address)");
+
+        // Mint tokens
+        let value_mint = U256::from(42e18);
+        let calldata_mint = get_calldata(selector_mint, (ALICE, value_mint).abi_encode());
+        run_tx(&mut db, &erc20, calldata_mint, &ALICE).unwrap();
+
+        // A regular call reads the ERC20 storage
+        let calldata_x_balance = get_calldata(selector_x_balance, (ALICE, erc20).abi_encode());
+        let balance_result = run_tx(&mut db, &erc20x, calldata_x_balance, &ALICE).unwrap();
+        assert_eq!(
+            U256::from_be_bytes::<32>(balance_result.output.as_slice().try_into().unwrap()),
+            value_mint,
+            "Incorrect balance"
+        );
+
+        // A delegate call runs the ERC20 code against the (empty) ERC20x storage
+        let calldata_x_delegate_balance =
+            get_calldata(selector_x_delegate_balance, (ALICE, erc20).abi_encode());
+        let delegate_result =
+            run_tx(&mut db, &erc20x, calldata_x_delegate_balance, &ALICE).unwrap();
+        assert_eq!(
+            U256::from_be_bytes::<32>(delegate_result.output.as_slice().try_into().unwrap()),
+            U256::ZERO,
+            "Incorrect balance"
+        );
+    }
+
+    #[test]
+    fn test_call_value_above_u64() {
+        let (mut db, _) = setup_erc20(ALICE);
+        let erc20x = setup_erc20x(&mut db);
+
+        // Fund the contract with more ETH than fits in a u64
+        let funds = U256::from(100e18);
+        assert!(funds > U256::from(u64::MAX));
+        db.load_account(erc20x).unwrap().info.balance = funds;
+
+        // Forward most of it
+        let value_send = U256::from(50e18);
+        let selector_send = get_selector_from_sig("x_send(address,uint256)");
+        let calldata_send = get_calldata(selector_send, (CAROL, value_send).abi_encode());
+        run_tx(&mut db, &erc20x, calldata_send, &ALICE).unwrap();
+
+        let carol_balance = db.load_account(CAROL).unwrap().info.balance;
+        assert_eq!(
+            carol_balance,
+            U256::from(1e18) + value_send,
+            "Incorrect balance"
+        );
+        let erc20x_balance = db.load_account(erc20x).unwrap().info.balance;
+        assert_eq!(erc20x_balance, funds - value_send, "Incorrect balance");
+    }
+
+    #[test]
+    fn test_selfdestruct() {
+        let (mut db, _) = setup_erc20(ALICE);
+        let erc20x = setup_erc20x(&mut db);
+
+        let funds = U256::from(5e18);
+        db.load_account(erc20x).unwrap().info.balance = funds;
+
+        let selector_destroy = get_selector_from_sig("x_destroy(address)");
+        let calldata_destroy = get_calldata(selector_destroy, CAROL.abi_encode());
+        run_tx(&mut db, &erc20x, calldata_destroy, &ALICE).unwrap();
+
+        // The whole balance is sent to the beneficiary
+        let carol_balance = db.load_account(CAROL).unwrap().info.balance;
+        assert_eq!(carol_balance, U256::from(1e18) + funds, "Incorrect balance");
+        let erc20x_balance = db.load_account(erc20x).unwrap().info.balance;
+        assert_eq!(erc20x_balance, U256::ZERO, "Incorrect balance");
+
+        // Since the contract wasn't created in the same tx, its code is kept (EIP-6780)
+        let erc20x_code_hash = db.load_account(erc20x).unwrap().info.code_hash;
+        assert_ne!(erc20x_code_hash, keccak256([]), "Contract was deleted");
+    }
+
+    #[test]
+    fn test_selfdestruct_in_creation_tx() {
+        initialize_logger();
+        let mut db = InMemoryDB::default();
+
+        // Fund the address of the first contract deployed (by Alice)
+        let funds = 1e18 as u64;
+        let ephemeral = ALICE.create(0);
+        add_balance_to_db(&mut db, ephemeral, funds);
+
+        let bytecode = get_bytecode("ephemeral");
+        let deployed = deploy_contract(&mut db, bytecode, Some(CAROL.abi_encode())).unwrap();
+        assert_eq!(deployed, ephemeral, "Incorrect address");
+
+        // The balance is sent to the beneficiary
+        let carol_balance = db.load_account(CAROL).unwrap().info.balance;
+        assert_eq!(carol_balance, U256::from(funds), "Incorrect balance");
+
+        // Since the contract was created in the same tx, it is deleted (EIP-6780)
+        let ephemeral_info = db.load_account(ephemeral).unwrap().info.clone();
+        assert!(ephemeral_info.is_empty(), "Contract wasn't deleted");
+    }
+
+    #[test]
+    fn test_static_state_change() {
+        let (mut db, _) = setup_erc20(ALICE);
+        let erc20x = setup_erc20x(&mut db);
+        let target = setup_erc20x(&mut db);
+
+        // `x_destroy` self-destructs, and `x_reenter` writes the transient reentrancy guard
+        let selector_x_destroy = get_selector_from_sig("x_destroy(address)");
+        let selector_x_reenter = get_selector_from_sig("x_reenter(bytes)");
+        let calls = [
+            get_calldata(selector_x_destroy, CAROL.abi_encode()),
+            get_calldata(selector_x_reenter, Bytes::new().abi_encode()),
+        ];
+
+        // State changes within a staticcall halt, burning all the gas given to the callee
+        let gas_limit = 1_000_000_u64;
+        let selector_x_staticcall =
+            get_selector_from_sig("x_staticcall_with_gas(address,bytes,uint64)");
+        for data in calls {
+            let calldata_x_staticcall = get_calldata(
+                selector_x_staticcall,
+                (target, Bytes::from(data), U64::from(gas_limit)).abi_encode(),
+            );
+            let call_result = run_tx(&mut db, &erc20x, calldata_x_staticcall, &ALICE).unwrap();
+            assert_eq!(
+                call_result.output,
+                false.abi_encode(),
+                "Staticcall succeeded"
+            );
+            assert!(call_result.gas_used > gas_limit, "Callee didn't halt");
+        }
+    }
+
+    #[test]
+    fn test_out_of_gas() {
+        let (mut db, _) = setup_erc20(ALICE);
+        let erc20x = setup_erc20x(&mut db);
+
+        // Attempt a call that never returns
+        let gas_limit = 1_000_000;
+        let selector_spin = get_selector_from_sig("spin()");
+        let calldata_spin = get_calldata(selector_spin, vec![]);
+        let spin_result = run_tx_with_gas_limit(&mut db, &erc20x, calldata_spin, &ALICE, gas_limit)
+            .expect_err("Tx succeeded");
+        assert!(spin_result.matches_out_of_gas(), "Incorrect error");
+    }
+
+    #[test]
+    fn test_call_gas_limit() {
+        let (mut db, _) = setup_erc20(ALICE);
+        let erc20x = setup_erc20x(&mut db);
+        let target = setup_erc20x(&mut db);
+
+        // A call that never returns can only burn the gas it was given
+        let gas_limit = 100_000_u64;
+        let selector_spin = get_selector_from_sig("spin()");
+        let selector_x_call = get_selector_from_sig("x_call_with_gas(address,bytes,uint64)");
+        let calldata_x_call = get_calldata(
+            selector_x_call,
+            (
+                target,
+                Bytes::from(selector_spin.to_vec()),
+                U64::from(gas_limit),
+            )
+                .abi_encode(),
+        );
+        let call_result = run_tx(&mut db, &erc20x, calldata_x_call, &ALICE).unwrap();
+        assert_eq!(call_result.output, false.abi_encode(), "Call succeeded");
+        assert!(
+            call_result.gas_used > gas_limit,
+            "Callee didn't burn its gas"
+        );
+        assert!(
+            call_result.gas_used < 10 * gas_limit,
+            "Callee burnt too much gas"
+        );
+    }
+
+    #[test]
+    fn test_nonreentrant() {
+        let (mut db, _) = setup_erc20(ALICE);
+        let erc20x = setup_erc20x(&mut db);
+
+        let selector_x_reenter = get_selector_from_sig("x_reenter(bytes)");
+        let selector_x_call = get_selector_from_sig("x_call_with_gas(address,bytes,uint64)");
+
+        // Reentering an unguarded method is allowed
+        let calldata_x_call = get_calldata(
+            selector_x_call,
+            (ALICE, Bytes::new(), U64::from(100_000)).abi_encode(),
+        );
+        let calldata_x_reenter = get_calldata(
+            selector_x_reenter,
+            Bytes::from(calldata_x_call).abi_encode(),
+        );
+        let reenter_result = run_tx(&mut db, &erc20x, calldata_x_reenter, &ALICE).unwrap();
+        assert_eq!(
+            reenter_result.output,
+            Bytes::new().abi_encode(),
+            "Reentrant call failed"
+        );
+
+        // Reentering a guarded method reverts with `ReentrancyGuardReentrantCall()`
+        let calldata_inner = get_calldata(selector_x_reenter, Bytes::new().abi_encode());
+        let calldata_x_reenter =
+            get_calldata(selector_x_reenter, Bytes::from(calldata_inner).abi_encode());
+        let reenter_result = run_tx(&mut db, &erc20x, calldata_x_reenter, &ALICE).unwrap();
+        assert_eq!(
+            reenter_result.output,
+            Bytes::from(hex::decode("3ee5aeb5").unwrap()).abi_encode(),
+            "Incorrect error"
+        );
+    }
+
+    #[test]
+    fn test_struct_error_and_event() {
+        let (mut db, _) = setup_erc20(ALICE);
+        let erc20x = setup_erc20x(&mut db);
+
+        let selector = get_selector_from_sig("x_check_limit(address,uint256,uint256)");
+        let (token, max) = (BOB, U256::from(100));
+
+        // Within the limit, the struct is emitted as a tuple
+        let calldata = get_calldata(selector, (token, max, U256::from(42)).abi_encode());
+        let result = run_tx(&mut db, &erc20x, calldata, &ALICE).unwrap();
+        let log = &result.logs[0];
+        let topics = log.data.topics();
+
+        let expected_event_hash = keccak256("LimitChecked(address,(address,uint256),uint256)");
+        assert_eq!(topics[0], expected_event_hash, "Incorrect event hash");
+        assert_eq!(
+            &topics[1][12..],
+            ALICE.as_slice(),
+            "Incorrect 'caller' in log"
+        );
+        assert_eq!(
+            log.data.data.to_vec(),
+            ((token, max), U256::from(42)).abi_encode_params(),
+            "Incorrect log data"
+        );
+
+        // Above the limit, all the fields of the named error are encoded as a tuple
+        let calldata = get_calldata(selector, (token, max, U256::from(420)).abi_encode());
+        let err = run_tx(&mut db, &erc20x, calldata, &ALICE).expect_err("Limit check succeeded");
+        assert!(
+            err.matches_custom_error_with_args(
+                "ExceedsLimit((address,uint256),uint256)",
+                ((token, max), U256::from(420)).abi_encode_params()
+            ),
+            "Incorrect error"
+        );
+    }
+
+    #[test]
+    fn test_anonymous_event() {
+        let (mut db, _) = setup_erc20(ALICE);
+        let erc20x = setup_erc20x(&mut db);
+
+        let selector = get_selector_from_sig("x_checkpoint(address,uint256,uint256)");
+        let (token, max, amount) = (BOB, U256::from(100), U256::from(42));
+
+        // No signature topic, so that the 4 indexed fields fit in the topics
+        let calldata = get_calldata(selector, (token, max, amount).abi_encode());
+        let result = run_tx(&mut db, &erc20x, calldata, &ALICE).unwrap();
+        let log = &result.logs[0];
+
+        let expected_topics = [
+            ALICE.into_word(),
+            token.into_word(),
+            B256::from(max.to_be_bytes()),
+            B256::from(amount.to_be_bytes()),
+        ];
+        assert_eq!(log.data.topics(), expected_topics, "Incorrect topics");
+        assert!(log.data.data.is_empty(), "Unexpected log data");
+    }
+
+    #[test]
+    fn test_indexed_dynamic_fields() {
+        let (mut db, _) = setup_erc20(ALICE);
+        let erc20x = setup_erc20x(&mut db);
+
+        let selector = get_selector_from_sig("x_note(string,address,uint256)");
+        let (memo, token, max) = ("limit raised".to_string(), BOB, U256::from(100));
+
+        let calldata = get_calldata(selector, (memo.clone(), token, max).abi_encode());
+        let result = run_tx(&mut db, &erc20x, calldata, &ALICE).unwrap();
+        let log = &result.logs[0];
+
+        // Strings are hashed as is, and structs as the concatenation of their encoded fields
+        let expected_topics = [
+            keccak256("Note(address,string,(address,uint256))"),
+            ALICE.into_word(),
+            keccak256(memo.as_bytes()),
+            keccak256(
+                [
+                    token.into_word().as_slice(),
+                    max.to_be_bytes::<32>().as_slice(),
+                ]
+                .concat(),
+            ),
+        ];
+        assert_eq!(log.data.topics(), expected_topics, "Incorrect topics");
+        assert!(log.data.data.is_empty(), "Unexpected log data");
+    }
+
+    #[test]
+    fn test_struct_array_event() {
+        let (mut db, _) = setup_erc20(ALICE);
+        let erc20x = setup_erc20x(&mut db);
+
+        let selector = get_selector_from_sig("x_check_limits(address[],uint256)");
+        let max = U256::from(100);
+
+        let calldata = get_calldata(selector, (vec![BOB, CAROL], max).abi_encode());
+        let result = run_tx(&mut db, &erc20x, calldata, &ALICE).unwrap();
+        let log = &result.logs[0];
+
+        let expected_event_hash = keccak256("LimitsChecked(address,(address,uint256)[])");
+        assert_eq!(
+            log.data.topics()[0],
+            expected_event_hash,
+            "Incorrect event hash"
+        );
+        assert_eq!(
+            log.data.data.to_vec(),
+            (vec![(BOB, max), (CAROL, max)],).abi_encode_params(),
+            "Incorrect log data"
+        );
+    }
+
     #[test]
     fn test_string_error() {
         let (mut db, erc20) = setup_erc20(ALICE);
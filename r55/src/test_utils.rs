@@ -7,11 +7,14 @@ pub use revm::{
 };
 use std::{fs, path::Path, sync::Once};
 
+use crate::exec::run_tx;
+
 static INIT: Once = Once::new();
 
 pub const ALICE: Address = address!("000000000000000000000000000000000000000A");
 pub const BOB: Address = address!("000000000000000000000000000000000000000B");
 pub const CAROL: Address = address!("000000000000000000000000000000000000000C");
+pub const DAVE: Address = address!("000000000000000000000000000000000000000D");
 
 pub fn initialize_logger() {
     INIT.call_once(|| {
@@ -53,6 +56,27 @@ pub fn get_calldata(selector: [u8; 4], mut args: Vec<u8>) -> Vec<u8> {
     calldata
 }
 
+pub fn call(db: &mut InMemoryDB, contract: Address, sig: &str, args: Vec<u8>) -> Vec<u8> {
+    call_from(db, contract, ALICE, sig, args)
+}
+
+pub fn call_from(
+    db: &mut InMemoryDB,
+    contract: Address,
+    caller: Address,
+    sig: &str,
+    args: Vec<u8>,
+) -> Vec<u8> {
+    let calldata = get_calldata(get_selector_from_sig(sig), args);
+    run_tx(db, &contract, calldata, &caller)
+        .expect("Error executing tx")
+        .output
+}
+
+pub fn address_word(address: Address) -> U256 {
+    U256::from_be_slice(address.as_slice())
+}
+
 pub fn get_mapping_slot(key_bytes: Vec<u8>, id: U256) -> U256 {
     let mut data_bytes = Vec::with_capacity(64);
     data_bytes.extend_from_slice(&key_bytes);
@@ -0,0 +1,188 @@
+use alloy_primitives::{keccak256, Address, U256};
+use alloy_sol_types::SolValue;
+use r55::{
+    exec::{deploy_contract, run_tx},
+    get_bytecode,
+    test_utils::{
+        address_word, call, get_calldata, get_mapping_slot, get_selector_from_sig,
+        initialize_logger, read_db_slot, ALICE, BOB, CAROL, DAVE,
+    },
+};
+use revm::InMemoryDB;
+
+fn allowlist_setup() -> (InMemoryDB, Address) {
+    initialize_logger();
+    let mut db = InMemoryDB::default();
+
+    // Deploy contract
+    let bytecode = get_bytecode("allowlist");
+    let allowlist = deploy_contract(&mut db, bytecode, Some(ALICE.abi_encode())).unwrap();
+
+    (db, allowlist)
+}
+
+#[test]
+fn test_enumerable_set() {
+    let (mut db, allowlist) = allowlist_setup();
+
+    for member in [BOB, CAROL, DAVE] {
+        let added = call(&mut db, allowlist, "add(address)", member.abi_encode());
+        assert_eq!(added, true.abi_encode(), "Member wasn't added");
+    }
+    let added = call(&mut db, allowlist, "add(address)", BOB.abi_encode());
+    assert_eq!(added, false.abi_encode(), "Member was added twice");
+
+    // Removing a member moves the last one to its place
+    let removed = call(&mut db, allowlist, "remove(address)", BOB.abi_encode());
+    assert_eq!(removed, true.abi_encode(), "Member wasn't removed");
+    let removed = call(&mut db, allowlist, "remove(address)", BOB.abi_encode());
+    assert_eq!(removed, false.abi_encode(), "Member was removed twice");
+
+    let members = call(&mut db, allowlist, "members()", vec![]);
+    assert_eq!(members, vec![DAVE, CAROL].abi_encode(), "Incorrect members");
+    let count = call(&mut db, allowlist, "member_count()", vec![]);
+    assert_eq!(count, U256::from(2).abi_encode(), "Incorrect member count");
+    for (index, member) in [DAVE, CAROL].iter().enumerate() {
+        let args = U256::from(index).abi_encode();
+        let member_at = call(&mut db, allowlist, "member_at(uint256)", args);
+        assert_eq!(member_at, member.abi_encode(), "Incorrect member at index");
+    }
+    for (member, expected) in [(BOB, false), (CAROL, true), (DAVE, true)] {
+        let is_member = call(
+            &mut db,
+            allowlist,
+            "is_member(address)",
+            member.abi_encode(),
+        );
+        assert_eq!(is_member, expected.abi_encode(), "Incorrect membership");
+    }
+
+    // Out-of-bounds indexes revert
+    let args = U256::from(2).abi_encode();
+    let calldata = get_calldata(get_selector_from_sig("member_at(uint256)"), args);
+    assert!(
+        run_tx(&mut db, &allowlist, calldata, &ALICE).is_err(),
+        "Query didn't revert"
+    );
+
+    // Storage matches OpenZeppelin's `EnumerableSet`: `_values` at slot 1, `_positions` at slot 2
+    let values_slot: U256 = keccak256(U256::from(1).to_be_bytes::<32>()).into();
+    let expected_words = [
+        (U256::from(1), U256::from(2)),
+        (values_slot, address_word(DAVE)),
+        (values_slot + U256::from(1), address_word(CAROL)),
+        (values_slot + U256::from(2), U256::ZERO),
+        (
+            get_mapping_slot(DAVE.abi_encode(), U256::from(2)),
+            U256::from(1),
+        ),
+        (
+            get_mapping_slot(CAROL.abi_encode(), U256::from(2)),
+            U256::from(2),
+        ),
+        (
+            get_mapping_slot(BOB.abi_encode(), U256::from(2)),
+            U256::ZERO,
+        ),
+    ];
+    for (slot, expected) in expected_words {
+        assert_eq!(
+            read_db_slot(&mut db, allowlist, slot),
+            expected,
+            "Incorrect storage layout"
+        );
+    }
+}
+
+#[test]
+fn test_enumerable_map() {
+    let (mut db, allowlist) = allowlist_setup();
+    let quota = U256::from(100);
+
+    for member in [BOB, CAROL] {
+        call(&mut db, allowlist, "add(address)", member.abi_encode());
+        let args = (member, quota).abi_encode();
+        let added = call(&mut db, allowlist, "set_quota(address,uint256)", args);
+        assert_eq!(added, true.abi_encode(), "Quota wasn't added");
+    }
+
+    // Updating a quota keeps its key
+    let updated_quota = U256::from(200);
+    let args = (CAROL, updated_quota).abi_encode();
+    let added = call(&mut db, allowlist, "set_quota(address,uint256)", args);
+    assert_eq!(added, false.abi_encode(), "Quota was added twice");
+
+    let quota_of = call(&mut db, allowlist, "quota_of(address)", CAROL.abi_encode());
+    assert_eq!(quota_of, updated_quota.abi_encode(), "Incorrect quota");
+    let quota_at = call(
+        &mut db,
+        allowlist,
+        "quota_at(uint256)",
+        U256::from(1).abi_encode(),
+    );
+    assert_eq!(
+        quota_at,
+        (CAROL, updated_quota).abi_encode(),
+        "Incorrect quota at index"
+    );
+
+    // Removing a member also removes its quota
+    call(&mut db, allowlist, "remove(address)", BOB.abi_encode());
+    let quota_at = call(
+        &mut db,
+        allowlist,
+        "quota_at(uint256)",
+        U256::ZERO.abi_encode(),
+    );
+    assert_eq!(
+        quota_at,
+        (CAROL, updated_quota).abi_encode(),
+        "Incorrect quota at index"
+    );
+
+    let calldata = get_calldata(get_selector_from_sig("quota_of(address)"), BOB.abi_encode());
+    assert!(
+        run_tx(&mut db, &allowlist, calldata, &ALICE).is_err(),
+        "Query didn't revert"
+    );
+
+    // Storage matches OpenZeppelin's `EnumerableMap`: `_keys` at slots 3-4, `_values` at slot 5
+    let keys_slot: U256 = keccak256(U256::from(3).to_be_bytes::<32>()).into();
+    let expected_words = [
+        (U256::from(3), U256::from(1)),
+        (keys_slot, address_word(CAROL)),
+        (
+            get_mapping_slot(CAROL.abi_encode(), U256::from(4)),
+            U256::from(1),
+        ),
+        (
+            get_mapping_slot(CAROL.abi_encode(), U256::from(5)),
+            updated_quota,
+        ),
+        (
+            get_mapping_slot(BOB.abi_encode(), U256::from(5)),
+            U256::ZERO,
+        ),
+    ];
+    for (slot, expected) in expected_words {
+        assert_eq!(
+            read_db_slot(&mut db, allowlist, slot),
+            expected,
+            "Incorrect storage layout"
+        );
+    }
+}
+
+#[test]
+fn test_only_admin() {
+    let (mut db, allowlist) = allowlist_setup();
+
+    let calldata = get_calldata(get_selector_from_sig("add(address)"), BOB.abi_encode());
+    assert!(
+        run_tx(&mut db, &allowlist, calldata, &BOB).is_err(),
+        "Non-admin added a member"
+    );
+
+    let count = call(&mut db, allowlist, "member_count()", vec![]);
+    assert_eq!(count, U256::ZERO.abi_encode(), "Incorrect member count");
+}
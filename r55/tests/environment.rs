@@ -0,0 +1,153 @@
+use alloy_primitives::{keccak256, Address, B256, U256};
+use alloy_sol_types::SolValue;
+use r55::{
+    exec::{deploy_contract, run_tx_with_block},
+    get_bytecode,
+    test_utils::{
+        add_balance_to_db, call, get_calldata, get_selector_from_sig, initialize_logger, ALICE, BOB,
+    },
+};
+use revm::{
+    primitives::{BlockEnv, KECCAK_EMPTY},
+    InMemoryDB,
+};
+
+fn environment_setup() -> (InMemoryDB, Address) {
+    initialize_logger();
+    let mut db = InMemoryDB::default();
+
+    // Fund user accounts with some ETH
+    add_balance_to_db(&mut db, ALICE, 1e18 as u64);
+
+    // Deploy contract
+    let bytecode = get_bytecode("environment");
+    let env = deploy_contract(&mut db, bytecode, None).unwrap();
+
+    (db, env)
+}
+
+fn call_in_block(
+    db: &mut InMemoryDB,
+    env: Address,
+    block: &BlockEnv,
+    sig: &str,
+    args: Vec<u8>,
+) -> Vec<u8> {
+    let calldata = get_calldata(get_selector_from_sig(sig), args);
+    run_tx_with_block(db, &env, calldata, &ALICE, block.clone())
+        .expect("Error executing tx")
+        .output
+}
+
+#[test]
+fn test_account_info() {
+    let (mut db, env) = environment_setup();
+    db.load_account(env).unwrap().info.balance = U256::from(42);
+
+    let this = call(&mut db, env, "this()", vec![]);
+    assert_eq!(this, env.abi_encode(), "Incorrect address");
+
+    let balance = call(&mut db, env, "balance(address)", ALICE.abi_encode());
+    assert_eq!(balance, U256::from(1e18).abi_encode(), "Incorrect balance");
+
+    let self_balance = call(&mut db, env, "self_balance()", vec![]);
+    assert_eq!(
+        self_balance,
+        U256::from(42).abi_encode(),
+        "Incorrect self balance"
+    );
+}
+
+#[test]
+fn test_account_code() {
+    let (mut db, env) = environment_setup();
+    let info = db.load_account(env).unwrap().info.clone();
+    let code = info.code.expect("Missing code").original_bytes();
+
+    // Contracts have code, EOAs don't
+    let is_contract = call(&mut db, env, "is_contract(address)", env.abi_encode());
+    assert_eq!(is_contract, true.abi_encode(), "Contract without code");
+    let is_contract = call(&mut db, env, "is_contract(address)", ALICE.abi_encode());
+    assert_eq!(is_contract, false.abi_encode(), "EOA with code");
+
+    let code_size = call(&mut db, env, "self_code_size()", vec![]);
+    assert_eq!(
+        code_size,
+        U256::from(code.len()).abi_encode(),
+        "Incorrect code size"
+    );
+
+    let code_hash = call(&mut db, env, "code_hash(address)", env.abi_encode());
+    assert_eq!(
+        code_hash,
+        info.code_hash.abi_encode(),
+        "Incorrect code hash"
+    );
+    let code_hash = call(&mut db, env, "code_hash(address)", ALICE.abi_encode());
+    assert_eq!(
+        code_hash,
+        KECCAK_EMPTY.abi_encode(),
+        "Incorrect EOA code hash"
+    );
+    let code_hash = call(&mut db, env, "code_hash(address)", BOB.abi_encode());
+    assert_eq!(
+        code_hash,
+        B256::ZERO.abi_encode(),
+        "Incorrect empty account code hash"
+    );
+
+    let code_head = call(&mut db, env, "code_head(address)", env.abi_encode());
+    assert_eq!(code_head, &code[..32], "Incorrect code");
+    let code_head = call(&mut db, env, "code_head(address)", ALICE.abi_encode());
+    assert_eq!(code_head, B256::ZERO.abi_encode(), "EOA with code");
+}
+
+#[test]
+fn test_block_and_tx_info() {
+    let (mut db, env) = environment_setup();
+
+    let coinbase = call(&mut db, env, "coinbase()", vec![]);
+    assert_eq!(coinbase, Address::ZERO.abi_encode(), "Incorrect coinbase");
+
+    let blob_base_fee = call(&mut db, env, "blob_base_fee()", vec![]);
+    assert_eq!(blob_base_fee.len(), 32, "Missing blob base fee");
+
+    // Only past blocks have a hash
+    let block_hash = call(&mut db, env, "block_hash(uint256)", U256::ZERO.abi_encode());
+    assert_eq!(block_hash, B256::ZERO.abi_encode(), "Incorrect block hash");
+
+    let gas_left = U256::from_be_slice(&call(&mut db, env, "gas_left()", vec![]));
+    assert!(gas_left > U256::ZERO, "No gas left");
+    assert!(gas_left < U256::from(100_000_000), "Too much gas left");
+}
+
+#[test]
+fn test_block_randomness_and_hashes() {
+    let (mut db, env) = environment_setup();
+    let block = BlockEnv {
+        number: U256::from(300),
+        prevrandao: Some(B256::repeat_byte(0x42)),
+        ..Default::default()
+    };
+
+    let prev_randao = call_in_block(&mut db, env, &block, "prev_randao()", vec![]);
+    assert_eq!(
+        prev_randao,
+        B256::repeat_byte(0x42).abi_encode(),
+        "Incorrect prevrandao"
+    );
+
+    // The in-memory db derives the hash of a block from its number
+    let number = U256::from(299);
+    let args = number.abi_encode();
+    let block_hash = call_in_block(&mut db, env, &block, "block_hash(uint256)", args);
+    let expected = keccak256(number.to_string().as_bytes());
+    assert_eq!(block_hash, expected.abi_encode(), "Incorrect block hash");
+
+    // Only the 256 most recent blocks (excluding the current one) have a hash
+    for number in [U256::from(300), U256::from(43)] {
+        let args = number.abi_encode();
+        let block_hash = call_in_block(&mut db, env, &block, "block_hash(uint256)", args);
+        assert_eq!(block_hash, B256::ZERO.abi_encode(), "Unexpected block hash");
+    }
+}
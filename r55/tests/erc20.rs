@@ -1,11 +1,11 @@
-use alloy_primitives::{Address, B256, U256};
+use alloy_primitives::{keccak256, Address, B256, U256, U8};
 use alloy_sol_types::SolValue;
 use r55::{
     exec::{deploy_contract, run_tx},
     get_bytecode,
     test_utils::{
-        add_balance_to_db, get_calldata, get_selector_from_sig, initialize_logger, ALICE, BOB,
-        CAROL,
+        add_balance_to_db, get_calldata, get_selector_from_sig, initialize_logger, read_db_slot,
+        ALICE, BOB, CAROL,
     },
 };
 use revm::InMemoryDB;
@@ -53,6 +53,91 @@ fn test_erc20_deployment() {
     );
 }
 
+#[test]
+fn test_erc20_metadata() {
+    let ERC20Setup {
+        mut db,
+        token,
+        owner,
+    } = erc20_setup(ALICE);
+    let selector_set = get_selector_from_sig("set_metadata(string,string,uint8)");
+    let (symbol, decimals) = ("R55".to_string(), U8::from(18));
+    let name_slot = U256::from(4);
+
+    // Short strings share the slot with their length
+    let name = "R55 Token".to_string();
+    let args = (name.clone(), symbol.clone(), decimals).abi_encode();
+    let calldata_set = get_calldata(selector_set, args);
+    run_tx(&mut db, &token, calldata_set, &owner).expect("Error executing tx");
+
+    let mut expected = [0u8; 32];
+    expected[..name.len()].copy_from_slice(name.as_bytes());
+    expected[31] = (name.len() * 2) as u8;
+    let name_word = read_db_slot(&mut db, token, name_slot);
+    assert_eq!(
+        name_word,
+        U256::from_be_bytes(expected),
+        "Incorrect short encoding"
+    );
+
+    for (sig, expected) in [
+        ("name()", name.abi_encode()),
+        ("symbol()", symbol.abi_encode()),
+        ("decimals()", decimals.abi_encode()),
+    ] {
+        let result = run_tx(&mut db, &token, get_selector_from_sig(sig).to_vec(), &owner)
+            .expect("Error executing tx")
+            .output;
+        assert_eq!(result, expected, "Incorrect {}", sig);
+    }
+
+    // Long strings keep their length in the slot, and their data at `keccak256(slot)`
+    let name = "R55 Token, a RISC-V smart contract token".to_string();
+    let args = (name.clone(), symbol.clone(), decimals).abi_encode();
+    let calldata_set = get_calldata(selector_set, args);
+    run_tx(&mut db, &token, calldata_set, &owner).expect("Error executing tx");
+
+    let data_slot: U256 = keccak256(name_slot.to_be_bytes::<32>()).into();
+    let mut second_chunk = [0u8; 32];
+    second_chunk[..name.len() - 32].copy_from_slice(&name.as_bytes()[32..]);
+    let expected_words = [
+        (name_slot, U256::from(name.len() * 2 + 1)),
+        (data_slot, U256::from_be_slice(&name.as_bytes()[..32])),
+        (data_slot + U256::from(1), U256::from_be_bytes(second_chunk)),
+    ];
+    for (slot, expected) in expected_words {
+        assert_eq!(
+            read_db_slot(&mut db, token, slot),
+            expected,
+            "Incorrect long encoding"
+        );
+    }
+
+    let name_result = run_tx(
+        &mut db,
+        &token,
+        get_selector_from_sig("name()").to_vec(),
+        &owner,
+    )
+    .expect("Error executing tx")
+    .output;
+    assert_eq!(name_result, name.abi_encode(), "Incorrect long name");
+
+    // Shortening a long string clears its data slots
+    let calldata_set = get_calldata(
+        selector_set,
+        (symbol.clone(), symbol, decimals).abi_encode(),
+    );
+    run_tx(&mut db, &token, calldata_set, &owner).expect("Error executing tx");
+    for slot in [data_slot, data_slot + U256::from(1)] {
+        assert_eq!(
+            read_db_slot(&mut db, token, slot),
+            U256::ZERO,
+            "Data slot wasn't cleared"
+        );
+    }
+}
+
 #[test]
 fn test_erc20_mint() {
     let ERC20Setup {
@@ -84,6 +169,83 @@ fn test_erc20_mint() {
     );
 }
 
+#[test]
+fn test_erc20_airdrop() {
+    // Returns the gas of each drop of an airdrop to `drops` distinct recipients. The calldata
+    // and its decoding are left out, by subtracting the gas of the same airdrop reverting with
+    // a zero amount, before the first drop.
+    let gas_per_drop = |drops: usize| {
+        let ERC20Setup {
+            mut db,
+            token,
+            owner,
+        } = erc20_setup(ALICE);
+
+        let recipients: Vec<Address> = (0..drops)
+            .map(|i| Address::from_word(B256::from(U256::from(0x1000 + i))))
+            .collect();
+        let amount = U256::from(1e18);
+
+        let selector_airdrop = get_selector_from_sig("airdrop(address[],uint256)");
+        let calldata_airdrop = get_calldata(
+            selector_airdrop,
+            (recipients.clone(), U256::ZERO).abi_encode(),
+        );
+        let result = run_tx(&mut db, &token, calldata_airdrop, &owner)
+            .expect_err("Airdrop transaction succeeded when it should fail");
+        assert!(
+            result.matches_custom_error("ZeroAmount"),
+            "Incorrect error signature"
+        );
+        let gas_reverted = result.gas_used().unwrap();
+
+        let calldata_airdrop =
+            get_calldata(selector_airdrop, (recipients.clone(), amount).abi_encode());
+        let airdrop_result =
+            run_tx(&mut db, &token, calldata_airdrop, &owner).expect("Error executing tx");
+        assert!(airdrop_result.status, "Airdrop transaction failed");
+        assert_eq!(
+            airdrop_result.logs.len(),
+            drops,
+            "Incorrect number of transfers"
+        );
+
+        // Verify balances and total supply
+        let selector_balance = get_selector_from_sig("balance_of(address)");
+        for recipient in [recipients[0], recipients[drops / 2], recipients[drops - 1]] {
+            let calldata_balance = get_calldata(selector_balance, recipient.abi_encode());
+            let balance = run_tx(&mut db, &token, calldata_balance, &owner)
+                .expect("Error executing tx")
+                .output;
+            assert_eq!(balance, amount.abi_encode(), "Incorrect balance");
+        }
+
+        let selector_supply = get_selector_from_sig("total_supply()");
+        let supply = run_tx(&mut db, &token, selector_supply.to_vec(), &owner)
+            .expect("Error executing tx")
+            .output;
+        let expected_supply = amount * U256::from(drops);
+        assert_eq!(
+            supply,
+            expected_supply.abi_encode(),
+            "Incorrect total supply"
+        );
+
+        (airdrop_result.gas_used - gas_reverted) / drops as u64
+    };
+
+    // Memory kept by each drop would be charged as memory expansion, which gets more expensive
+    // the more memory is used. Only the last pages touched by the drops, whose cost is spread
+    // over all of them, may differ.
+    let (few, many) = (gas_per_drop(100), gas_per_drop(2_000));
+    assert!(
+        many <= few + 5,
+        "Gas per drop grows with the number of drops: {} (2000 drops) vs {} (100 drops)",
+        many,
+        few
+    );
+}
+
 #[test]
 fn test_erc20_transfer() {
     let ERC20Setup {
@@ -241,7 +403,7 @@ fn test_erc20_transfer_insufficient_balance() {
 
     assert!(
         result.matches_custom_error_with_args(
-            "ERC20Error::InsufficientBalance(uint256)",
+            "InsufficientBalance(uint256)",
             mint_amount.abi_encode()
         ),
         "Incorrect error signature"
@@ -286,7 +448,7 @@ fn test_erc20_transfer_from_insufficient_allowance() {
     assert!(transfer_amount > approve_amount);
     assert!(
         result.matches_custom_error_with_args(
-            "ERC20Error::InsufficientAllowance(uint256)",
+            "InsufficientAllowance(uint256)",
             approve_amount.abi_encode()
         ),
         "Incorrect error signature"
@@ -330,7 +492,7 @@ fn test_erc20_transfer_from_insufficient_balance() {
     assert!(transfer_amount > mint_amount);
     assert!(
         result.matches_custom_error_with_args(
-            "ERC20Error::InsufficientBalance(uint256)",
+            "InsufficientBalance(uint256)",
             mint_amount.abi_encode()
         ),
         "Incorrect error signature"
@@ -355,7 +517,7 @@ fn test_erc20_mint_unauthorized() {
         .expect_err("Mint transaction succeeded when it should fail");
 
     assert!(
-        result.matches_custom_error("ERC20Error::OnlyOwner"),
+        result.matches_custom_error("OnlyOwner"),
         "Incorrect error signature"
     );
 }
@@ -378,7 +540,7 @@ fn test_erc20_zero_address_checks() {
         .expect_err("Mint transaction succeeded when it should fail");
 
     assert!(
-        result.matches_custom_error("ERC20Error::ZeroAddress"),
+        result.matches_custom_error("ZeroAddress"),
         "Incorrect error signature"
     );
 
@@ -391,7 +553,7 @@ fn test_erc20_zero_address_checks() {
         .expect_err("Transfer transaction succeeded when it should fail");
 
     assert!(
-        result.matches_custom_error("ERC20Error::ZeroAddress"),
+        result.matches_custom_error("ZeroAddress"),
         "Incorrect error signature"
     );
 }
@@ -414,7 +576,7 @@ fn test_erc20_zero_amount_checks() {
         .expect_err("Mint transaction succeeded when it should fail");
 
     assert!(
-        result.matches_custom_error("ERC20Error::ZeroAmount"),
+        result.matches_custom_error("ZeroAmount"),
         "Incorrect error signature"
     );
 
@@ -426,7 +588,7 @@ fn test_erc20_zero_amount_checks() {
         .expect_err("Transfer transaction succeeded when it should fail");
 
     assert!(
-        result.matches_custom_error("ERC20Error::ZeroAmount"),
+        result.matches_custom_error("ZeroAmount"),
         "Incorrect error signature"
     );
 }
@@ -448,7 +610,7 @@ fn test_erc20_self_approval() {
         .expect_err("Approve transaction succeeded when it should fail");
 
     assert!(
-        result.matches_custom_error("ERC20Error::SelfApproval"),
+        result.matches_custom_error("SelfApproval"),
         "Incorrect error signature"
     );
 }
@@ -476,7 +638,7 @@ fn test_erc20_self_transfer() {
         .expect_err("Transfer transaction succeeded when it should fail");
 
     assert!(
-        result.matches_custom_error("ERC20Error::SelfTransfer"),
+        result.matches_custom_error("SelfTransfer"),
         "Incorrect error signature"
     );
 
@@ -500,7 +662,7 @@ fn test_erc20_self_transfer() {
         .expect_err("TransferFrom transaction succeeded when it should fail");
 
     assert!(
-        result.matches_custom_error("ERC20Error::SelfTransfer"),
+        result.matches_custom_error("SelfTransfer"),
         "Incorrect error signature"
     );
 }
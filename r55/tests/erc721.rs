@@ -1,11 +1,11 @@
-use alloy_primitives::{Address, B256, U256};
+use alloy_primitives::{keccak256, Address, B256, U256};
 use alloy_sol_types::SolValue;
 use r55::{
     exec::{deploy_contract, run_tx},
     get_bytecode,
     test_utils::{
-        add_balance_to_db, get_calldata, get_selector_from_sig, initialize_logger, ALICE, BOB,
-        CAROL,
+        add_balance_to_db, get_calldata, get_mapping_slot, get_selector_from_sig,
+        initialize_logger, read_db_slot, ALICE, BOB, CAROL,
     },
 };
 use revm::InMemoryDB;
@@ -70,6 +70,19 @@ fn test_erc721_mint() {
     let mint_result = run_tx(&mut db, &token, calldata_mint, &owner).expect("Error executing tx");
     assert!(mint_result.status, "Mint transaction failed");
 
+    // Verify the `Transfer` log keeps all 3 indexed fields
+    let expected_topics = [
+        keccak256("Transfer(address,address,uint256)"),
+        Address::ZERO.into_word(),
+        recipient.into_word(),
+        B256::from(token_id.to_be_bytes()),
+    ];
+    assert_eq!(
+        mint_result.logs[0].data.topics(),
+        expected_topics,
+        "Incorrect topics"
+    );
+
     // Verify ownership
     let selector_owner_of = get_selector_from_sig("owner_of(uint256)");
     let calldata_owner = get_calldata(selector_owner_of, token_id.abi_encode());
@@ -99,6 +112,87 @@ fn test_erc721_mint() {
     );
 }
 
+#[test]
+fn test_erc721_token_uri_and_enumeration() {
+    let ERC721Setup {
+        mut db,
+        token,
+        owner,
+    } = erc721_setup(ALICE);
+    let token_ids = [U256::from(7), U256::from(3)];
+
+    let selector_mint = get_selector_from_sig("mint(address,uint256)");
+    for token_id in token_ids {
+        let calldata_mint = get_calldata(selector_mint, (BOB, token_id).abi_encode());
+        run_tx(&mut db, &token, calldata_mint, &owner).expect("Error executing tx");
+    }
+
+    // Minted tokens are pushed to a vec, whose elements start at `keccak256(slot)`
+    let all_tokens_slot = U256::from(9);
+    let data_slot: U256 = keccak256(all_tokens_slot.to_be_bytes::<32>()).into();
+    assert_eq!(
+        read_db_slot(&mut db, token, all_tokens_slot),
+        U256::from(2),
+        "Incorrect length"
+    );
+    for (i, token_id) in token_ids.iter().enumerate() {
+        let element = read_db_slot(&mut db, token, data_slot + U256::from(i));
+        assert_eq!(element, *token_id, "Incorrect element");
+
+        let selector_by_index = get_selector_from_sig("token_by_index(uint256)");
+        let calldata_by_index = get_calldata(selector_by_index, U256::from(i).abi_encode());
+        let by_index_result = run_tx(&mut db, &token, calldata_by_index, &owner)
+            .expect("Error executing tx")
+            .output;
+        assert_eq!(
+            by_index_result,
+            token_id.abi_encode(),
+            "Incorrect token by index"
+        );
+    }
+
+    let selector_by_index = get_selector_from_sig("token_by_index(uint256)");
+    let calldata_by_index = get_calldata(selector_by_index, U256::from(2).abi_encode());
+    let result = run_tx(&mut db, &token, calldata_by_index, &owner)
+        .expect_err("Index query succeeded when it should fail");
+    assert!(
+        result.matches_custom_error("NotMinted"),
+        "Incorrect error signature"
+    );
+
+    // Token URIs are strings stored as mapping values
+    let uri = "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string();
+    let selector_set_uri = get_selector_from_sig("set_token_uri(uint256,string)");
+    let calldata_set_uri = get_calldata(selector_set_uri, (token_ids[0], uri.clone()).abi_encode());
+    run_tx(&mut db, &token, calldata_set_uri, &owner).expect("Error executing tx");
+
+    let uri_slot = get_mapping_slot(token_ids[0].abi_encode(), U256::from(8));
+    let uri_header = read_db_slot(&mut db, token, uri_slot);
+    assert_eq!(
+        uri_header,
+        U256::from(uri.len() * 2 + 1),
+        "Incorrect long encoding"
+    );
+
+    let selector_uri = get_selector_from_sig("token_uri(uint256)");
+    for (token_id, expected) in [(token_ids[0], uri.clone()), (token_ids[1], String::new())] {
+        let calldata_uri = get_calldata(selector_uri, token_id.abi_encode());
+        let uri_result = run_tx(&mut db, &token, calldata_uri, &owner)
+            .expect("Error executing tx")
+            .output;
+        assert_eq!(uri_result, expected.abi_encode(), "Incorrect token URI");
+    }
+
+    // Only minted tokens can have a URI
+    let calldata_set_uri = get_calldata(selector_set_uri, (U256::from(999), uri).abi_encode());
+    let result = run_tx(&mut db, &token, calldata_set_uri, &owner)
+        .expect_err("URI update succeeded when it should fail");
+    assert!(
+        result.matches_custom_error("NotMinted"),
+        "Incorrect error signature"
+    );
+}
+
 #[test]
 fn test_erc721_approve_and_transfer_from() {
     let ERC721Setup {
@@ -212,7 +306,7 @@ fn test_erc721_mint_already_exists() {
         .expect_err("Mint transaction succeeded when it should fail");
 
     assert!(
-        result.matches_custom_error("ERC721Error::AlreadyMinted"),
+        result.matches_custom_error("AlreadyMinted"),
         "Incorrect error signature"
     );
 }
@@ -246,7 +340,7 @@ fn test_erc721_unauthorized_transfer() {
         .expect_err("Transfer transaction succeeded when it should fail");
 
     assert!(
-        result.matches_custom_error("ERC721Error::Unauthorized"),
+        result.matches_custom_error("Unauthorized"),
         "Incorrect error signature"
     );
 }
@@ -280,7 +374,7 @@ fn test_erc721_wrong_from_address() {
         .expect_err("Transfer transaction succeeded when it should fail");
 
     assert!(
-        result.matches_custom_error("ERC721Error::WrongFrom"),
+        result.matches_custom_error("WrongFrom"),
         "Incorrect error signature"
     );
 }
@@ -303,7 +397,7 @@ fn test_erc721_zero_address_checks() {
         .expect_err("Mint transaction succeeded when it should fail");
 
     assert!(
-        result.matches_custom_error("ERC721Error::ZeroAddress"),
+        result.matches_custom_error("ZeroAddress"),
         "Incorrect error signature"
     );
 
@@ -325,7 +419,7 @@ fn test_erc721_zero_address_checks() {
         .expect_err("Transfer transaction succeeded when it should fail");
 
     assert!(
-        result.matches_custom_error("ERC721Error::ZeroAddress"),
+        result.matches_custom_error("ZeroAddress"),
         "Incorrect error signature"
     );
 }
@@ -346,7 +440,7 @@ fn test_erc721_balance_of_zero_address() {
         .expect_err("Balance query succeeded when it should fail");
 
     assert!(
-        result.matches_custom_error("ERC721Error::ZeroAddress"),
+        result.matches_custom_error("ZeroAddress"),
         "Incorrect error signature"
     );
 }
@@ -367,7 +461,7 @@ fn test_erc721_query_non_existent_token() {
         .expect_err("Owner query succeeded when it should fail");
 
     assert!(
-        result.matches_custom_error("ERC721Error::NotMinted"),
+        result.matches_custom_error("NotMinted"),
         "Incorrect error signature"
     );
 }
@@ -0,0 +1,141 @@
+use alloy_primitives::{Address, Bytes, U256};
+use alloy_sol_types::SolValue;
+use r55::{
+    exec::{deploy_contract, run_tx},
+    get_bytecode,
+    test_utils::{
+        call, get_calldata, get_selector_from_sig, initialize_logger, read_db_slot, ALICE, CAROL,
+    },
+};
+use revm::InMemoryDB;
+
+// The same contract, built on the default storage path and with the storage cache
+const COUNTERS: [&str; 2] = ["counter", "counter_cached"];
+
+fn counter_setup(name: &str) -> (InMemoryDB, Address) {
+    initialize_logger();
+    let mut db = InMemoryDB::default();
+
+    // Deploy contract
+    let bytecode = get_bytecode(name);
+    let counter = deploy_contract(&mut db, bytecode, None).unwrap();
+
+    (db, counter)
+}
+
+#[test]
+fn test_flush_on_return() {
+    for name in COUNTERS {
+        let (mut db, counter) = counter_setup(name);
+
+        call(
+            &mut db,
+            counter,
+            "increment(uint256)",
+            U256::from(3).abi_encode(),
+        );
+        let count = read_db_slot(&mut db, counter, U256::ZERO);
+        assert_eq!(count, U256::from(3), "Incorrect count in {}", name);
+        let count = call(&mut db, counter, "count()", vec![]);
+        assert_eq!(
+            count,
+            U256::from(3).abi_encode(),
+            "Incorrect count in {}",
+            name
+        );
+    }
+}
+
+#[test]
+fn test_flush_on_selfdestruct() {
+    for name in COUNTERS {
+        let (mut db, counter) = counter_setup(name);
+        call(
+            &mut db,
+            counter,
+            "increment(uint256)",
+            U256::from(1).abi_encode(),
+        );
+
+        // Since the contract wasn't created in the same tx, its storage is kept (EIP-6780)
+        let args = (U256::from(2), CAROL).abi_encode();
+        call(
+            &mut db,
+            counter,
+            "increment_and_destroy(uint256,address)",
+            args,
+        );
+        let count = read_db_slot(&mut db, counter, U256::ZERO);
+        assert_eq!(count, U256::from(3), "Incorrect count in {}", name);
+    }
+}
+
+#[test]
+fn test_discard_on_revert() {
+    for name in COUNTERS {
+        let (mut db, counter) = counter_setup(name);
+        call(
+            &mut db,
+            counter,
+            "increment(uint256)",
+            U256::from(1).abi_encode(),
+        );
+
+        let selector = get_selector_from_sig("increment_and_revert(uint256)");
+        let calldata = get_calldata(selector, U256::from(2).abi_encode());
+        let result = run_tx(&mut db, &counter, calldata, &ALICE);
+        assert!(result.is_err(), "Tx didn't revert in {}", name);
+
+        let count = read_db_slot(&mut db, counter, U256::ZERO);
+        assert_eq!(count, U256::from(1), "Incorrect count in {}", name);
+    }
+}
+
+#[test]
+fn test_reentrant_write() {
+    for name in COUNTERS {
+        let (mut db, counter) = counter_setup(name);
+
+        // The increment of the re-entrant call must be seen (and kept) after the call returns
+        let selector = get_selector_from_sig("increment(uint256)");
+        let data = Bytes::from(get_calldata(selector, U256::from(1).abi_encode()));
+        let count = call(
+            &mut db,
+            counter,
+            "increment_around(bytes)",
+            data.abi_encode(),
+        );
+        assert_eq!(
+            count,
+            U256::from(3).abi_encode(),
+            "Incorrect count in {}",
+            name
+        );
+
+        let count = read_db_slot(&mut db, counter, U256::ZERO);
+        assert_eq!(count, U256::from(3), "Incorrect count in {}", name);
+    }
+}
+
+#[test]
+fn test_cache_gas() {
+    let [gas_default, gas_cached] = COUNTERS.map(|name| {
+        let (mut db, counter) = counter_setup(name);
+
+        let selector = get_selector_from_sig("increment(uint256)");
+        let calldata = get_calldata(selector, U256::from(100).abi_encode());
+        let result = run_tx(&mut db, &counter, calldata, &ALICE).expect("Error executing tx");
+        let count = read_db_slot(&mut db, counter, U256::ZERO);
+        assert_eq!(count, U256::from(100), "Incorrect count in {}", name);
+
+        result.gas_used
+    });
+
+    // The cached counter only loads and stores its slot once, instead of on every increment
+    assert!(
+        gas_cached < gas_default,
+        "Cache didn't reduce gas: {} (cached) vs {} (default)",
+        gas_cached,
+        gas_default
+    );
+}
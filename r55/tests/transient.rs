@@ -0,0 +1,70 @@
+use alloy_primitives::{Address, U256};
+use alloy_sol_types::SolValue;
+use r55::{
+    exec::{deploy_contract, run_tx},
+    get_bytecode,
+    test_utils::{get_calldata, get_selector_from_sig, initialize_logger, ALICE},
+};
+use revm::InMemoryDB;
+
+fn transient_setup() -> (InMemoryDB, Address) {
+    initialize_logger();
+    let mut db = InMemoryDB::default();
+
+    // Deploy contract
+    let bytecode = get_bytecode("transient");
+    let contract = deploy_contract(&mut db, bytecode, None).unwrap();
+
+    (db, contract)
+}
+
+#[test]
+fn test_transient_storage() {
+    let (mut db, contract) = transient_setup();
+    let (first, second) = (U256::from(20), U256::from(22));
+
+    // Transient values accumulate within a tx
+    let selector_add_twice = get_selector_from_sig("add_twice(uint256,uint256)");
+    let calldata_add_twice = get_calldata(selector_add_twice, (first, second).abi_encode());
+    let add_result = run_tx(&mut db, &contract, calldata_add_twice, &ALICE)
+        .expect("Error executing tx")
+        .output;
+    assert_eq!(
+        add_result,
+        (first + second).abi_encode(),
+        "Incorrect pending amount"
+    );
+
+    // ...and are cleared once it ends
+    let selector_pending = get_selector_from_sig("pending()");
+    let pending_result = run_tx(&mut db, &contract, selector_pending.to_vec(), &ALICE)
+        .expect("Error executing tx")
+        .output;
+    assert_eq!(
+        pending_result,
+        U256::ZERO.abi_encode(),
+        "Pending amount not cleared"
+    );
+
+    let selector_pending_of = get_selector_from_sig("pending_of(address)");
+    let calldata_pending_of = get_calldata(selector_pending_of, ALICE.abi_encode());
+    let pending_of_result = run_tx(&mut db, &contract, calldata_pending_of, &ALICE)
+        .expect("Error executing tx")
+        .output;
+    assert_eq!(
+        pending_of_result,
+        U256::ZERO.abi_encode(),
+        "Pending amount not cleared"
+    );
+
+    // Persistent storage is unaffected
+    let selector_total = get_selector_from_sig("total()");
+    let total_result = run_tx(&mut db, &contract, selector_total.to_vec(), &ALICE)
+        .expect("Error executing tx")
+        .output;
+    assert_eq!(
+        total_result,
+        (first + second).abi_encode(),
+        "Incorrect total"
+    );
+}
@@ -0,0 +1,348 @@
+use alloy_primitives::{keccak256, Address, Bytes, U128, U16, U256, U64, U8};
+use alloy_sol_types::SolValue;
+use r55::{
+    exec::deploy_contract,
+    get_bytecode,
+    test_utils::{
+        address_word, call, call_from, get_mapping_slot, initialize_logger, read_db_slot, ALICE,
+        BOB, CAROL, DAVE,
+    },
+};
+use revm::InMemoryDB;
+
+fn vault_setup(fee_bps: U16) -> (InMemoryDB, Address) {
+    initialize_logger();
+    let mut db = InMemoryDB::default();
+
+    // Deploy contract
+    let constructor = (ALICE, fee_bps).abi_encode();
+    let bytecode = get_bytecode("vault");
+    let vault = deploy_contract(&mut db, bytecode, Some(constructor)).unwrap();
+
+    (db, vault)
+}
+
+#[test]
+fn test_packed_slots() {
+    let fee_bps = U16::from(30);
+    let (mut db, vault) = vault_setup(fee_bps);
+
+    // `admin`, `paused` and `fee_bps` share slot 0
+    let expected = address_word(ALICE) | (U256::from(30) << 168);
+    assert_eq!(
+        read_db_slot(&mut db, vault, U256::ZERO),
+        expected,
+        "Incorrect packed slot"
+    );
+
+    // Updating a packed value preserves its neighbours
+    call(&mut db, vault, "set_paused(bool)", true.abi_encode());
+    let expected = expected | (U256::from(1) << 160);
+    assert_eq!(
+        read_db_slot(&mut db, vault, U256::ZERO),
+        expected,
+        "Incorrect packed slot"
+    );
+
+    let admin = call(&mut db, vault, "admin()", vec![]);
+    assert_eq!(admin, ALICE.abi_encode(), "Incorrect admin");
+    let paused = call(&mut db, vault, "paused()", vec![]);
+    assert_eq!(paused, true.abi_encode(), "Incorrect paused flag");
+    let fee = call(&mut db, vault, "fee_bps()", vec![]);
+    assert_eq!(fee, fee_bps.abi_encode(), "Incorrect fee");
+}
+
+#[test]
+fn test_packed_array() {
+    let (mut db, vault) = vault_setup(U16::ZERO);
+    let limits = [U128::from(100), U128::from(200), U128::from(300)];
+
+    for (index, limit) in limits.iter().enumerate() {
+        let args = (U256::from(index), *limit).abi_encode();
+        call(&mut db, vault, "set_limit(uint256,uint128)", args);
+    }
+
+    // Two `U128` values fit in a slot, so the array spans slots 2 and 3
+    let slot_2 = U256::from(100) | (U256::from(200) << 128);
+    assert_eq!(
+        read_db_slot(&mut db, vault, U256::from(2)),
+        slot_2,
+        "Incorrect slot 2"
+    );
+    assert_eq!(
+        read_db_slot(&mut db, vault, U256::from(3)),
+        U256::from(300),
+        "Incorrect slot 3"
+    );
+
+    for (index, limit) in limits.iter().enumerate() {
+        let args = U256::from(index).abi_encode();
+        let limit_result = call(&mut db, vault, "limit(uint256)", args);
+        assert_eq!(limit_result, limit.abi_encode(), "Incorrect limit");
+    }
+}
+
+#[test]
+fn test_struct_mapping() {
+    let (mut db, vault) = vault_setup(U16::ZERO);
+    let (amount, unlock_at) = (U256::from(1e18), U64::from(1_700_000_000));
+
+    let args = (amount, unlock_at).abi_encode();
+    call_from(&mut db, vault, BOB, "deposit(uint256,uint64)", args.clone());
+    call_from(&mut db, vault, BOB, "deposit(uint256,uint64)", args);
+
+    let position = call(&mut db, vault, "position(address)", BOB.abi_encode());
+    let expected = (amount * U256::from(2), BOB, true, unlock_at).abi_encode();
+    assert_eq!(position, expected, "Incorrect position");
+    let total = call(&mut db, vault, "total()", vec![]);
+    assert_eq!(
+        total,
+        (amount * U256::from(2)).abi_encode(),
+        "Incorrect total"
+    );
+
+    // The struct starts at the mapping slot of its key, and packs its small fields
+    let base = get_mapping_slot(BOB.abi_encode(), U256::from(4));
+    let packed = address_word(BOB) | (U256::from(1) << 160) | (U256::from(1_700_000_000) << 168);
+    assert_eq!(
+        read_db_slot(&mut db, vault, base),
+        amount * U256::from(2),
+        "Incorrect amount"
+    );
+    assert_eq!(
+        read_db_slot(&mut db, vault, base + U256::from(1)),
+        packed,
+        "Incorrect packing"
+    );
+}
+
+// Slot where the data of a vec or a long bytes value starts
+fn data_slot(slot: U256) -> U256 {
+    keccak256(slot.to_be_bytes::<32>()).into()
+}
+
+#[test]
+fn test_address_vec() {
+    let (mut db, vault) = vault_setup(U16::ZERO);
+    let (depositors_slot, args) = (U256::from(5), (U256::from(1), U64::ZERO).abi_encode());
+
+    // Addresses can't share a slot, so each depositor takes its own one
+    for depositor in [BOB, CAROL, DAVE, BOB] {
+        call_from(
+            &mut db,
+            vault,
+            depositor,
+            "deposit(uint256,uint64)",
+            args.clone(),
+        );
+    }
+    let data = data_slot(depositors_slot);
+    assert_eq!(
+        read_db_slot(&mut db, vault, depositors_slot),
+        U256::from(3),
+        "Incorrect length"
+    );
+    for (i, depositor) in [BOB, CAROL, DAVE].into_iter().enumerate() {
+        let element = read_db_slot(&mut db, vault, data + U256::from(i));
+        assert_eq!(element, address_word(depositor), "Incorrect element");
+    }
+
+    // Removing the first depositor pops the last one, and sets it in its place
+    call(
+        &mut db,
+        vault,
+        "remove_depositor(uint256)",
+        U256::ZERO.abi_encode(),
+    );
+    let expected_words = [
+        (depositors_slot, U256::from(2)),
+        (data, address_word(DAVE)),
+        (data + U256::from(1), address_word(CAROL)),
+        (data + U256::from(2), U256::ZERO),
+    ];
+    for (slot, expected) in expected_words {
+        assert_eq!(
+            read_db_slot(&mut db, vault, slot),
+            expected,
+            "Incorrect vec storage"
+        );
+    }
+
+    // Removing the last depositor only pops it
+    call(
+        &mut db,
+        vault,
+        "remove_depositor(uint256)",
+        U256::from(1).abi_encode(),
+    );
+    assert_eq!(
+        read_db_slot(&mut db, vault, depositors_slot),
+        U256::from(1),
+        "Incorrect length"
+    );
+    assert_eq!(
+        read_db_slot(&mut db, vault, data + U256::from(1)),
+        U256::ZERO,
+        "Slot not cleared"
+    );
+
+    let depositors = call(&mut db, vault, "depositors()", vec![]);
+    assert_eq!(depositors, vec![DAVE].abi_encode(), "Incorrect depositors");
+}
+
+#[test]
+fn test_packed_vec() {
+    let (mut db, vault) = vault_setup(U16::ZERO);
+    let unlocks_slot = U256::from(6);
+
+    for at in 1..=5 {
+        call(
+            &mut db,
+            vault,
+            "schedule_unlock(uint64)",
+            U64::from(at).abi_encode(),
+        );
+    }
+
+    // Four `U64` values fit in a slot, so the 5th one starts the second slot
+    let data = data_slot(unlocks_slot);
+    let first_slot =
+        U256::from(1) | (U256::from(2) << 64) | (U256::from(3) << 128) | (U256::from(4) << 192);
+    assert_eq!(
+        read_db_slot(&mut db, vault, unlocks_slot),
+        U256::from(5),
+        "Incorrect length"
+    );
+    assert_eq!(
+        read_db_slot(&mut db, vault, data),
+        first_slot,
+        "Incorrect first slot"
+    );
+    let second_slot = read_db_slot(&mut db, vault, data + U256::from(1));
+    assert_eq!(second_slot, U256::from(5), "Incorrect second slot");
+
+    // Setting an element preserves its neighbours
+    let args = (U256::from(1), U64::from(20)).abi_encode();
+    call(&mut db, vault, "reschedule_unlock(uint256,uint64)", args);
+    let first_slot = (first_slot ^ (U256::from(2) << 64)) | (U256::from(20) << 64);
+    assert_eq!(
+        read_db_slot(&mut db, vault, data),
+        first_slot,
+        "Incorrect first slot"
+    );
+
+    // Popping an element only clears its own bytes
+    let popped = call(&mut db, vault, "cancel_unlock()", vec![]);
+    assert_eq!(
+        popped,
+        U64::from(5).abi_encode(),
+        "Incorrect popped element"
+    );
+    assert_eq!(
+        read_db_slot(&mut db, vault, data + U256::from(1)),
+        U256::ZERO,
+        "Slot not cleared"
+    );
+
+    let popped = call(&mut db, vault, "cancel_unlock()", vec![]);
+    assert_eq!(
+        popped,
+        U64::from(4).abi_encode(),
+        "Incorrect popped element"
+    );
+    let first_slot = first_slot ^ (U256::from(4) << 192);
+    assert_eq!(
+        read_db_slot(&mut db, vault, data),
+        first_slot,
+        "Incorrect first slot"
+    );
+    assert_eq!(
+        read_db_slot(&mut db, vault, unlocks_slot),
+        U256::from(3),
+        "Incorrect length"
+    );
+
+    let unlocks = call(&mut db, vault, "unlocks()", vec![]);
+    let expected = vec![U64::from(1), U64::from(20), U64::from(3)];
+    assert_eq!(unlocks, expected.abi_encode(), "Incorrect unlocks");
+}
+
+#[test]
+fn test_bytes_boundary() {
+    let (mut db, vault) = vault_setup(U16::ZERO);
+    let (memo_slot, memo) = (U256::from(7), (1..=33).collect::<Vec<u8>>());
+    let data = data_slot(memo_slot);
+
+    // 31 bytes still fit in the slot, next to their length
+    call(
+        &mut db,
+        vault,
+        "set_memo(bytes)",
+        Bytes::from(memo[..31].to_vec()).abi_encode(),
+    );
+    let mut short = [0u8; 32];
+    short[..31].copy_from_slice(&memo[..31]);
+    short[31] = 31 * 2;
+    let header = read_db_slot(&mut db, vault, memo_slot);
+    assert_eq!(
+        header,
+        U256::from_be_bytes(short),
+        "Incorrect short encoding"
+    );
+
+    // Pushing the 32nd and 33rd bytes moves the data to `keccak256(slot)`
+    for byte in &memo[31..] {
+        call(
+            &mut db,
+            vault,
+            "push_memo(uint8)",
+            U8::from(*byte).abi_encode(),
+        );
+    }
+    let expected_words = [
+        (memo_slot, U256::from(33 * 2 + 1)),
+        (data, U256::from_be_slice(&memo[..32])),
+        (data + U256::from(1), U256::from(33) << 248),
+    ];
+    for (slot, expected) in expected_words {
+        assert_eq!(
+            read_db_slot(&mut db, vault, slot),
+            expected,
+            "Incorrect long encoding"
+        );
+    }
+    let result = call(&mut db, vault, "memo()", vec![]);
+    assert_eq!(
+        result,
+        Bytes::from(memo.clone()).abi_encode(),
+        "Incorrect memo"
+    );
+
+    // Popping back to 31 bytes moves the data into the slot, and clears the data slots
+    for byte in memo[31..].iter().rev() {
+        let popped = call(&mut db, vault, "pop_memo()", vec![]);
+        assert_eq!(
+            popped,
+            U8::from(*byte).abi_encode(),
+            "Incorrect popped byte"
+        );
+    }
+    let expected_words = [
+        (memo_slot, U256::from_be_bytes(short)),
+        (data, U256::ZERO),
+        (data + U256::from(1), U256::ZERO),
+    ];
+    for (slot, expected) in expected_words {
+        assert_eq!(
+            read_db_slot(&mut db, vault, slot),
+            expected,
+            "Incorrect short encoding"
+        );
+    }
+    let result = call(&mut db, vault, "memo()", vec![]);
+    assert_eq!(
+        result,
+        Bytes::from(memo[..31].to_vec()).abi_encode(),
+        "Incorrect memo"
+    );
+}
@@ -0,0 +1,80 @@
+use alloy_primitives::{Address, U256};
+use alloy_sol_types::SolValue;
+use r55::{
+    exec::{deploy_contract, run_tx},
+    get_bytecode,
+    test_utils::{call, get_calldata, get_selector_from_sig, initialize_logger, ALICE},
+};
+use revm::InMemoryDB;
+
+fn wallet_setup() -> (InMemoryDB, Address, Address) {
+    initialize_logger();
+    let mut db = InMemoryDB::default();
+
+    // Deploy contracts
+    let wallet = deploy_contract(&mut db, get_bytecode("wallet"), None).unwrap();
+    let erc20x = deploy_contract(&mut db, get_bytecode("erc20x"), None).unwrap();
+
+    (db, wallet, erc20x)
+}
+
+#[test]
+fn test_receive() {
+    let (mut db, wallet, erc20x) = wallet_setup();
+    let value = U256::from(1e18);
+    db.load_account(erc20x).unwrap().info.balance = value;
+
+    // Plain ETH transfers are routed to the receive handler
+    call(
+        &mut db,
+        erc20x,
+        "x_send(address,uint256)",
+        (wallet, value).abi_encode(),
+    );
+    let received = call(&mut db, wallet, "received()", vec![]);
+    assert_eq!(received, value.abi_encode(), "Incorrect received amount");
+    let wallet_balance = db.load_account(wallet).unwrap().info.balance;
+    assert_eq!(wallet_balance, value, "Incorrect balance");
+
+    // Empty calldata without value is also accepted
+    run_tx(&mut db, &wallet, vec![], &ALICE).expect("Error executing tx");
+    let fallbacks = call(&mut db, wallet, "fallbacks()", vec![]);
+    assert_eq!(fallbacks, U256::ZERO.abi_encode(), "Fallback was called");
+}
+
+#[test]
+fn test_fallback() {
+    let (mut db, wallet, _) = wallet_setup();
+
+    // Unknown selectors are routed to the fallback handler
+    run_tx(&mut db, &wallet, vec![0xde, 0xad, 0xbe, 0xef], &ALICE).expect("Error executing tx");
+    let fallbacks = call(&mut db, wallet, "fallbacks()", vec![]);
+    assert_eq!(
+        fallbacks,
+        U256::from(1).abi_encode(),
+        "Fallback wasn't called"
+    );
+
+    // ...and so is calldata that is too short to hold a selector
+    run_tx(&mut db, &wallet, vec![0xde, 0xad], &ALICE).expect("Error executing tx");
+    let fallbacks = call(&mut db, wallet, "fallbacks()", vec![]);
+    assert_eq!(
+        fallbacks,
+        U256::from(2).abi_encode(),
+        "Fallback wasn't called"
+    );
+}
+
+#[test]
+fn test_no_receive() {
+    let (mut db, _, erc20x) = wallet_setup();
+    db.load_account(erc20x).unwrap().info.balance = U256::from(1e18);
+
+    // Contracts without ETH handlers reject plain transfers
+    let calldata = get_calldata(
+        get_selector_from_sig("x_send(address,uint256)"),
+        (erc20x, U256::from(1)).abi_encode(),
+    );
+    let send_result = run_tx(&mut db, &erc20x, calldata, &ALICE);
+    assert!(send_result.is_err(), "Transfer succeeded");
+}